@@ -23,6 +23,74 @@ impl Builder {{
     }
 }
 
+/// Options whose argument has a typed representation in the crate, these take
+/// `impl Into<String>` instead of `&str`
+const INTO_STRING: &[&str] = &[
+    "format",
+    "format-sort",
+    "output",
+    "match-filters",
+    "break-match-filters",
+    "date",
+    "datebefore",
+    "dateafter",
+    "playlist-items",
+    "download-sections",
+    "sponsorblock-mark",
+    "sponsorblock-remove",
+    "sponsorblock-chapter-title",
+    "use-postprocessor",
+    "postprocessor-args",
+    "audio-format",
+    "audio-quality",
+];
+
+/// Options whose argument is a number, size or duration, with the parameter type and how it is
+/// turned into an argument (`{}` is replaced by the parameter name)
+const TYPED: &[(&str, &str, &str)] = &[
+    ("socket-timeout", "Duration", "{}.as_secs_f64().to_string()"),
+    (
+        "min-filesize",
+        "impl Into<ByteSize>",
+        "{}.into().to_string()",
+    ),
+    (
+        "max-filesize",
+        "impl Into<ByteSize>",
+        "{}.into().to_string()",
+    ),
+    ("age-limit", "u32", "{}.to_string()"),
+    ("max-downloads", "u32", "{}.to_string()"),
+    ("concurrent-fragments", "u32", "{}.to_string()"),
+    ("limit-rate", "impl Into<ByteSize>", "{}.into().to_string()"),
+    (
+        "throttled-rate",
+        "impl Into<ByteSize>",
+        "{}.into().to_string()",
+    ),
+    ("retries", "impl Into<Retries>", "{}.into().to_string()"),
+    (
+        "file-access-retries",
+        "impl Into<Retries>",
+        "{}.into().to_string()",
+    ),
+    (
+        "fragment-retries",
+        "impl Into<Retries>",
+        "{}.into().to_string()",
+    ),
+    (
+        "buffer-size",
+        "impl Into<ByteSize>",
+        "{}.into().to_string()",
+    ),
+    (
+        "http-chunk-size",
+        "impl Into<ByteSize>",
+        "{}.into().to_string()",
+    ),
+];
+
 #[derive(Debug)]
 pub struct Opt {
     option: String,
@@ -43,6 +111,12 @@ impl ToString for Opt {
             s => s,
         };
 
+        let into_string = INTO_STRING.contains(&self.option.as_str());
+        let typed = TYPED
+            .iter()
+            .find(|(option, _, _)| *option == self.option)
+            .map(|(_, ty, conversion)| (ty, conversion));
+
         format!(
             r#"    /// {}
     pub fn {}(&mut self{}) -> &mut Self {{
@@ -53,12 +127,25 @@ impl ToString for Opt {
             name,
             arg_label
                 .as_ref()
-                .map(|arg| format!(", {arg}: &str"))
+                .map(|arg| match (into_string, typed) {
+                    (_, Some((ty, _))) => format!(", {arg}: {ty}"),
+                    (true, None) => format!(", {arg}: impl Into<String>"),
+                    (false, None) => format!(", {arg}: &str"),
+                })
                 .unwrap_or(String::new()),
             self.option,
             arg_label
                 .as_ref()
-                .map(|arg| format!("\n        self.command_mut().arg({arg});"))
+                .map(|arg| match (into_string, typed) {
+                    (_, Some((_, conversion))) => format!(
+                        "\n        self.command_mut().arg({});",
+                        conversion.replace("{}", arg)
+                    ),
+                    (true, None) => {
+                        format!("\n        self.command_mut().arg::<String>({arg}.into());")
+                    }
+                    (false, None) => format!("\n        self.command_mut().arg({arg});"),
+                })
                 .unwrap_or(String::new()),
         )
     }
@@ -220,7 +307,9 @@ fn main() {
         .open("../src/command/options.rs")
         .unwrap();
 
-    file.write_all("use super::Builder;\n\n".as_bytes())
-        .unwrap();
+    file.write_all(
+        "use std::time::Duration;\n\nuse super::{Builder, ByteSize, Retries};\n\n".as_bytes(),
+    )
+    .unwrap();
     file.write_all(out.as_bytes()).unwrap();
 }
@@ -0,0 +1,206 @@
+use std::collections::BTreeMap;
+
+use crate::{Message, MessageType};
+
+/// Environment printed by yt-dlp at the start of a run with `--verbose`, see
+/// [`DebugHeader::from_messages`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DebugHeader {
+    /// `Command-line config`, the arguments yt-dlp was run with
+    pub command_line: Vec<String>,
+    /// e.g. `2024.08.06`
+    pub version: String,
+    /// Release channel, e.g. `stable` or `nightly`
+    pub channel: Option<String>,
+    /// Repository the build comes from, e.g. `yt-dlp/yt-dlp`
+    pub origin: Option<String>,
+    /// Abbreviated git commit of the build
+    pub git_head: Option<String>,
+    /// How yt-dlp was installed, e.g. `pip`, `zip` or `linux_exe`
+    pub variant: Option<String>,
+    /// e.g. `3.12.4`
+    pub python_version: Option<String>,
+    /// e.g. `Linux-6.9.7-arch1-1-x86_64-with-glibc2.39`
+    pub platform: Option<String>,
+    /// `exe versions`, e.g. ffmpeg and ffprobe
+    pub executables: Vec<Dependency>,
+    /// `Optional libraries`, e.g. `brotli` and `certifi`
+    pub libraries: Vec<Dependency>,
+    /// `Proxy map`, from scheme (or `all`) to the proxy URL
+    pub proxies: BTreeMap<String, String>,
+    /// `Request Handlers`, e.g. `urllib` and `requests`
+    pub request_handlers: Vec<String>,
+    pub plugin_directories: Vec<String>,
+    /// Number of extractors loaded
+    pub extractors: Option<u32>,
+}
+
+/// An executable or Python library found by yt-dlp
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub name: String,
+    pub version: String,
+    /// Features of the build, e.g. `setts` for ffmpeg
+    pub features: Vec<String>,
+}
+
+impl DebugHeader {
+    /// Collect the header from `[debug]` messages, `None` if the messages do not contain the
+    /// `yt-dlp version` line
+    pub fn from_messages<'a>(messages: impl IntoIterator<Item = &'a Message>) -> Option<Self> {
+        let mut header = Self::default();
+        let mut has_version = false;
+
+        for message in messages {
+            if message.message_type() != MessageType::Debug {
+                continue;
+            }
+
+            let content = message.content().trim();
+            if let Some(version) = content.strip_prefix("yt-dlp version ") {
+                header.parse_version(version);
+                has_version = true;
+            } else if let Some(python) = content.strip_prefix("Python ") {
+                header.parse_python(python);
+            } else if let Some((key, value)) = content.split_once(": ") {
+                header.parse_field(key, value.trim());
+            } else if let Some(count) = content
+                .strip_prefix("Loaded ")
+                .and_then(|count| count.strip_suffix(" extractors"))
+            {
+                header.extractors = count.parse().ok();
+            }
+        }
+
+        has_version.then_some(header)
+    }
+
+    /// An executable from `exe versions` by name, e.g. `ffmpeg`
+    pub fn executable(&self, name: &str) -> Option<&Dependency> {
+        self.executables.iter().find(|exe| exe.name == name)
+    }
+
+    /// A library from `Optional libraries` by name, e.g. `brotli`
+    pub fn library(&self, name: &str) -> Option<&Dependency> {
+        self.libraries.iter().find(|library| library.name == name)
+    }
+
+    /// `stable@2024.08.06 from yt-dlp/yt-dlp [4d9231208] (pip)`, only the version is required
+    fn parse_version(&mut self, s: &str) {
+        let mut words = s.split_whitespace();
+
+        let version = words.next().unwrap_or_default();
+        match version.split_once('@') {
+            Some((channel, version)) => {
+                self.channel = Some(channel.to_string());
+                self.version = version.to_string();
+            }
+            None => self.version = version.to_string(),
+        }
+
+        while let Some(word) = words.next() {
+            if word == "from" {
+                self.origin = words.next().map(str::to_string);
+            } else if let Some(head) = word.strip_prefix('[').and_then(|w| w.strip_suffix(']')) {
+                self.git_head = Some(head.to_string());
+            } else if let Some(variant) = word.strip_prefix('(').and_then(|w| w.strip_suffix(')')) {
+                self.variant.get_or_insert_with(|| variant.to_string());
+            }
+        }
+    }
+
+    /// `3.12.4 (CPython x86_64 64bit) - Linux-6.9.7-x86_64-with-glibc2.39 (OpenSSL 3.3.1, ...)`
+    fn parse_python(&mut self, s: &str) {
+        self.python_version = s.split_whitespace().next().map(str::to_string);
+        self.platform = s.split_once(" - ").map(|(_, platform)| {
+            match platform.split_once(" (") {
+                Some((platform, _)) => platform,
+                None => platform,
+            }
+            .to_string()
+        });
+    }
+
+    fn parse_field(&mut self, key: &str, value: &str) {
+        match key {
+            "Command-line config" => self.command_line = python_strings(value),
+            "exe versions" => {
+                self.executables = list(value)
+                    .map(|exe| {
+                        let (name, version) = exe.split_once(' ').unwrap_or((exe, ""));
+                        let (version, features) = match version.split_once(" (") {
+                            Some((version, features)) => (
+                                version,
+                                features
+                                    .trim_end_matches(')')
+                                    .split(',')
+                                    .map(str::to_string)
+                                    .collect(),
+                            ),
+                            None => (version, Vec::new()),
+                        };
+
+                        Dependency {
+                            name: name.to_string(),
+                            version: version.to_string(),
+                            features,
+                        }
+                    })
+                    .collect()
+            }
+            "Optional libraries" => {
+                self.libraries = list(value)
+                    .map(|library| {
+                        let (name, version) = library.rsplit_once('-').unwrap_or((library, ""));
+                        Dependency {
+                            name: name.to_string(),
+                            version: version.to_string(),
+                            features: Vec::new(),
+                        }
+                    })
+                    .collect()
+            }
+            "Proxy map" => {
+                let strings = python_strings(value);
+                self.proxies = strings
+                    .chunks_exact(2)
+                    .map(|pair| (pair[0].clone(), pair[1].clone()))
+                    .collect();
+            }
+            "Request Handlers" => self.request_handlers = list(value).map(str::to_string).collect(),
+            "Plugin directories" => self.plugin_directories = python_strings(value),
+            _ => {}
+        }
+    }
+}
+
+/// Comma separated list, `none` if empty
+fn list(s: &str) -> impl Iterator<Item = &str> {
+    s.split(", ")
+        .map(str::trim)
+        .filter(|item| !item.is_empty() && *item != "none")
+}
+
+/// String literals in the `repr` of a Python list or dict, e.g. `['-v', "it's"]`
+fn python_strings(s: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\'' && c != '"' {
+            continue;
+        }
+
+        let mut string = String::new();
+        while let Some(next) = chars.next() {
+            match next {
+                '\\' => string.extend(chars.next()),
+                next if next == c => break,
+                next => string.push(next),
+            }
+        }
+        strings.push(string);
+    }
+
+    strings
+}
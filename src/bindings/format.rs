@@ -0,0 +1,76 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+
+/// One entry of the `formats` array in info JSON, the same formats `--list-formats` prints as a
+/// table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Format {
+    pub format_id: String,
+    pub format_note: Option<String>,
+    pub ext: Option<String>,
+    pub url: Option<String>,
+    pub protocol: Option<String>,
+    /// Video codec, `"none"` for formats without video
+    pub vcodec: Option<String>,
+    /// Audio codec, `"none"` for formats without audio
+    pub acodec: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub fps: Option<f64>,
+    /// Total bitrate in KBit/s
+    pub tbr: Option<f64>,
+    /// Audio bitrate in KBit/s
+    pub abr: Option<f64>,
+    /// Video bitrate in KBit/s
+    pub vbr: Option<f64>,
+    /// Audio sampling rate in Hz
+    pub asr: Option<u32>,
+    pub audio_channels: Option<u32>,
+    #[serde(default, deserialize_with = "bytes")]
+    pub filesize: Option<u64>,
+    #[serde(default, deserialize_with = "bytes")]
+    pub filesize_approx: Option<u64>,
+    /// `"SDR"`, `"HDR10"`, `"HLG"` etc.
+    pub dynamic_range: Option<String>,
+    pub language: Option<String>,
+
+    /// Every field not covered above
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Format {
+    /// Whether the format has audio but no video
+    pub fn is_audio_only(&self) -> bool {
+        self.vcodec.as_deref() == Some("none") && self.acodec.as_deref() != Some("none")
+    }
+
+    /// Whether the format has video but no audio
+    pub fn is_video_only(&self) -> bool {
+        self.acodec.as_deref() == Some("none") && self.vcodec.as_deref() != Some("none")
+    }
+
+    /// Resolution as shown by `--list-formats`, e.g. `1920x1080`, `720p` or `audio only`
+    pub fn resolution(&self) -> String {
+        if self.is_audio_only() {
+            return "audio only".to_string();
+        }
+
+        match (self.width, self.height) {
+            (Some(width), Some(height)) => format!("{width}x{height}"),
+            (None, Some(height)) => format!("{height}p"),
+            (Some(width), None) => format!("{width}x?"),
+            (None, None) => "unknown".to_string(),
+        }
+    }
+
+    /// Exact file size if known, otherwise the estimate
+    pub fn size(&self) -> Option<u64> {
+        self.filesize.or(self.filesize_approx)
+    }
+}
+
+/// Byte counts are sometimes estimated as floats by extractors
+fn bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+    Option::<f64>::deserialize(deserializer).map(|bytes| bytes.map(|bytes| bytes as u64))
+}
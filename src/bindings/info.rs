@@ -0,0 +1,197 @@
+use std::{collections::HashMap, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{ParseError, ParseErrorVariant};
+
+use super::Format;
+
+/// Output of `--dump-json` or `--dump-single-json`, either a single video or a playlist
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Info {
+    Video(Box<VideoInfo>),
+    Playlist(Box<PlaylistInfo>),
+}
+
+/// Metadata of a single video, as printed by `--dump-json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VideoInfo {
+    pub id: String,
+    pub title: Option<String>,
+    pub fulltitle: Option<String>,
+    pub description: Option<String>,
+    pub uploader: Option<String>,
+    pub uploader_id: Option<String>,
+    pub uploader_url: Option<String>,
+    pub channel: Option<String>,
+    pub channel_id: Option<String>,
+    pub upload_date: Option<String>,
+    #[serde(default, deserialize_with = "seconds")]
+    pub timestamp: Option<i64>,
+    pub duration: Option<f64>,
+    pub view_count: Option<u64>,
+    pub like_count: Option<u64>,
+    pub is_live: Option<bool>,
+    pub webpage_url: Option<String>,
+    pub extractor: Option<String>,
+    pub extractor_key: Option<String>,
+    pub ext: Option<String>,
+    pub format_id: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub formats: Vec<Format>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub thumbnails: Vec<Thumbnail>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub subtitles: HashMap<String, Vec<Subtitle>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub automatic_captions: HashMap<String, Vec<Subtitle>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub chapters: Vec<Chapter>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub requested_downloads: Vec<RequestedDownload>,
+
+    /// Every field not covered above
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Metadata of a playlist, as printed by `--dump-single-json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlaylistInfo {
+    pub id: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub uploader: Option<String>,
+    pub uploader_id: Option<String>,
+    pub channel: Option<String>,
+    pub channel_id: Option<String>,
+    pub webpage_url: Option<String>,
+    pub extractor: Option<String>,
+    pub extractor_key: Option<String>,
+    pub playlist_count: Option<u64>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub thumbnails: Vec<Thumbnail>,
+    /// Videos and nested playlists, `None` for entries yt-dlp could not extract, e.g. unavailable
+    /// videos skipped with `--ignore-errors`
+    #[serde(default, deserialize_with = "nullable")]
+    pub entries: Vec<Option<Info>>,
+
+    /// Every field not covered above
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl PlaylistInfo {
+    /// Every video in the playlist, including those in nested playlists
+    pub fn videos(&self) -> Box<dyn Iterator<Item = &VideoInfo> + '_> {
+        Box::new(self.entries.iter().flatten().flat_map(|entry| match entry {
+            Info::Video(video) => Box::new(std::iter::once(video.as_ref())),
+            Info::Playlist(playlist) => playlist.videos(),
+        }))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Thumbnail {
+    pub url: String,
+    pub id: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub preference: Option<i64>,
+    pub resolution: Option<String>,
+}
+
+/// A subtitle track in one format, keyed by language in [`VideoInfo::subtitles`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Subtitle {
+    pub ext: Option<String>,
+    pub url: Option<String>,
+    pub name: Option<String>,
+    /// Subtitle content, for extractors that embed subtitles instead of linking to them
+    pub data: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chapter {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub title: Option<String>,
+}
+
+/// A download yt-dlp has decided on, after format selection and output templating
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequestedDownload {
+    pub format_id: Option<String>,
+    pub ext: Option<String>,
+    /// Final path of the file, only known once it has been downloaded
+    pub filepath: Option<String>,
+    /// Predicted path of the file
+    pub filename: Option<String>,
+
+    /// Every field not covered above
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Treats an explicit `null` the same as a missing field
+fn nullable<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Option::unwrap_or_default)
+}
+
+/// Timestamps are sometimes given as floats by extractors
+fn seconds<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+    Option::<f64>::deserialize(deserializer).map(|seconds| seconds.map(|seconds| seconds as i64))
+}
+
+fn from_json<T: for<'de> Deserialize<'de>>(s: &str) -> Result<T, ParseError> {
+    serde_json::from_str(s)
+        .map_err(|e| ParseError::new(s.to_string(), ParseErrorVariant::InvalidInfo(e.to_string())))
+}
+
+impl<'de> Deserialize<'de> for Info {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+
+        let is_playlist = matches!(
+            value.get("_type").and_then(Value::as_str),
+            Some("playlist" | "multi_video")
+        );
+        let info = if is_playlist {
+            serde_json::from_value(value).map(|info| Self::Playlist(Box::new(info)))
+        } else {
+            serde_json::from_value(value).map(|info| Self::Video(Box::new(info)))
+        };
+
+        info.map_err(serde::de::Error::custom)
+    }
+}
+
+impl FromStr for Info {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_json(s)
+    }
+}
+
+impl FromStr for VideoInfo {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_json(s)
+    }
+}
+
+impl FromStr for PlaylistInfo {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_json(s)
+    }
+}
@@ -0,0 +1,153 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{Message, MessageType, PostProcessorName};
+
+use super::ytdlp_error::split_video_id;
+
+/// A step of a download reported by a neutral message, see [`LifecycleEvent::from_message`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LifecycleEvent {
+    /// `[youtube] ID: Downloading webpage` and the other steps of an extractor,
+    /// `Extracting URL: ...` is the first of them
+    Extracting {
+        extractor: String,
+        video_id: Option<String>,
+        step: String,
+    },
+    /// `[info] ID: Downloading 1 format(s): 137+140`, the IDs of merged formats are joined by `+`
+    FormatsChosen {
+        video_id: String,
+        formats: Vec<String>,
+    },
+    /// `[download] Destination: file.mp4`
+    Destination(PathBuf),
+    /// `[Merger] Merging formats into "file.mkv"`
+    Merging(PathBuf),
+    /// A step of a post-processor, with the file it writes to if it reports one, e.g.
+    /// `[ExtractAudio] Destination: file.mp3` or `[MoveFiles] Moving file "a" to "b"`
+    PostProcess {
+        post_processor: PostProcessorName,
+        destination: Option<PathBuf>,
+    },
+    /// `[download] file.mp4 has already been downloaded`
+    AlreadyDownloaded(PathBuf),
+    /// `Deleting original file file.webm (pass -k to keep)`
+    DeletingOriginal(PathBuf),
+}
+
+impl LifecycleEvent {
+    /// Classify a neutral message, `None` for warnings, errors and messages that are not
+    /// lifecycle events
+    pub fn from_message(message: &Message) -> Option<Self> {
+        if message.message_type() != MessageType::Neutral {
+            return None;
+        }
+
+        let tag = message.tag();
+        let content = message.content().trim();
+
+        if let Some(path) = content
+            .strip_prefix("Deleting original file ")
+            .map(|path| path.trim_end_matches(" (pass -k to keep)"))
+        {
+            return Some(Self::DeletingOriginal(path.into()));
+        }
+
+        match tag {
+            "download" => {
+                if let Some(path) = content.strip_prefix("Destination: ") {
+                    Some(Self::Destination(path.into()))
+                } else {
+                    content
+                        .find(" has already been downloaded")
+                        .map(|end| Self::AlreadyDownloaded(content[..end].into()))
+                }
+            }
+            "info" => {
+                let (video_id, text) = split_video_id(message);
+                let (_, formats) = text
+                    .strip_prefix("Downloading ")?
+                    .split_once(" format(s): ")?;
+
+                Some(Self::FormatsChosen {
+                    video_id: video_id?,
+                    formats: formats.split(", ").map(str::to_string).collect(),
+                })
+            }
+            "Merger" => content
+                .strip_prefix("Merging formats into ")
+                .map(|path| Self::Merging(unquote(path).into())),
+            "MoveFiles" => {
+                let destination = content
+                    .strip_prefix("Moving file ")
+                    .and_then(|files| files.rsplit_once(" to "))
+                    .map(|(_, to)| unquote(to).into());
+
+                Some(Self::PostProcess {
+                    post_processor: PostProcessorName::Other(tag.to_string()),
+                    destination,
+                })
+            }
+            _ => {
+                let post_processor = PostProcessorName::from_str(tag).ok()?;
+                let destination = content
+                    .split_once("Destination: ")
+                    .map(|(_, path)| PathBuf::from(path));
+
+                match post_processor {
+                    // unknown tags are extractors, unless they report a destination like a
+                    // post-processor from a plugin would
+                    PostProcessorName::Other(_) if destination.is_none() => {
+                        extraction_step(message)
+                    }
+                    post_processor => Some(Self::PostProcess {
+                        post_processor,
+                        destination,
+                    }),
+                }
+            }
+        }
+    }
+
+    /// File the event is about, if any
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::Destination(path)
+            | Self::Merging(path)
+            | Self::AlreadyDownloaded(path)
+            | Self::DeletingOriginal(path) => Some(path),
+            Self::PostProcess { destination, .. } => destination.as_deref(),
+            Self::Extracting { .. } | Self::FormatsChosen { .. } => None,
+        }
+    }
+}
+
+/// `[extractor] ID: Downloading ...` or `[extractor] Extracting URL: ...`
+fn extraction_step(message: &Message) -> Option<LifecycleEvent> {
+    if message.tag().is_empty() {
+        return None;
+    }
+
+    let (video_id, step) = match message.content().trim() {
+        content if content.starts_with("Extracting URL: ") => (None, content),
+        _ => split_video_id(message),
+    };
+
+    (step.starts_with("Downloading ") || step.starts_with("Extracting URL: ")).then(|| {
+        LifecycleEvent::Extracting {
+            extractor: message.tag().to_string(),
+            video_id,
+            step: step.to_string(),
+        }
+    })
+}
+
+/// Paths are quoted in some messages, e.g. `Merging formats into "file.mkv"`
+fn unquote(path: &str) -> &str {
+    path.strip_prefix('"')
+        .and_then(|path| path.strip_suffix('"'))
+        .unwrap_or(path)
+}
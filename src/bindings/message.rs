@@ -1,56 +1,67 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
 
 use crate::{ParseError, ParseErrorVariant};
 
+/// A line of text printed by yt-dlp, e.g. `WARNING: [youtube] abc: nsig extraction failed`
+///
+/// The type and the tag are both optional, `ERROR: Unable to ...` has no tag and
+/// `Deleting original file ...` has neither.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Message {
     r#type: MessageType,
     tag: String,
     content: String,
+    line: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MessageType {
     Warning,
     Error,
+    /// `DEBUG:` or `[debug]`, printed with `--verbose`
+    Debug,
     Neutral,
 }
 
 impl FromStr for Message {
     type Err = ParseError;
 
+    /// Parses `TYPE: [tag] content`, ignoring ANSI escape codes and all but the last redraw of a
+    /// line with carriage returns
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut r#type = String::new();
-        let mut tag = String::new();
-        let mut content = String::new();
-
-        // 0 - type
-        // 1 - between type and tag
-        // 2 - tag
-        // 3 - content
-        let mut stage: u8 = 0;
-
-        for c in s.chars() {
-            match stage {
-                0 if c == ':' => stage = 1,
-                0 | 1 if c == '[' => stage = 2,
-                0 => r#type.push(c),
-                1 => {
-                    return Err(ParseError::new(
-                        s.to_string(),
-                        ParseErrorVariant::NonspaceBeforeTags,
-                    ))
-                }
-                2 if c == ']' => stage = 3,
-                2 => tag.push(c),
-                3 => content.push(c),
-                _ => unreachable!(),
+        let cleaned = clean_line(s);
+        let rest = cleaned.trim_start();
+
+        // only the known types are read as such, e.g. `URL: ...` is untagged content
+        let (r#type, rest) = match rest.split_once(':') {
+            Some((r#type @ ("WARNING" | "ERROR" | "DEBUG"), rest)) => (
+                MessageType::from_str(r#type).map_err(|e| e.replace(s.to_string()))?,
+                rest.trim_start(),
+            ),
+            _ => (MessageType::Neutral, rest),
+        };
+
+        // tags never contain whitespace, which tells them apart from content such as `[Errno 2]`
+        let (tag, content) = match rest.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+            Some((tag, content))
+                if !tag.is_empty() && !tag.contains(|c: char| c.is_whitespace() || c == '[') =>
+            {
+                (tag, content.trim_start())
             }
-        }
+            _ => ("", rest),
+        };
 
-        Ok(Self::new(
-            MessageType::from_str(&r#type).map_err(|e| e.replace(s.to_string()))?,
-            tag,
-            content,
-        ))
+        let r#type = match r#type {
+            MessageType::Neutral if tag == "debug" => MessageType::Debug,
+            r#type => r#type,
+        };
+
+        Ok(Self {
+            r#type,
+            tag: tag.to_string(),
+            content: content.to_string(),
+            line: s.to_string(),
+        })
     }
 }
 
@@ -61,6 +72,7 @@ impl FromStr for MessageType {
         Ok(match s {
             "WARNING" => Self::Warning,
             "ERROR" => Self::Error,
+            "DEBUG" => Self::Debug,
             "" => Self::Neutral,
             _ => {
                 return Err(ParseError::new(
@@ -74,10 +86,91 @@ impl FromStr for MessageType {
 
 impl Message {
     pub fn new(r#type: MessageType, tag: String, content: String) -> Self {
-        Self {
+        let mut message = Self {
             r#type,
             tag,
             content,
+            line: String::new(),
+        };
+        message.line = message.to_string();
+        message
+    }
+
+    pub fn message_type(&self) -> MessageType {
+        self.r#type
+    }
+
+    /// Text between the square brackets, usually the extractor or component that reported the
+    /// message, e.g. `youtube`, empty if the message has no tag
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Text after the type and the tag
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// The line as printed by yt-dlp, including escape codes
+    pub fn line(&self) -> &str {
+        &self.line
+    }
+}
+
+impl Display for MessageType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Warning => "WARNING",
+            Self::Error => "ERROR",
+            Self::Debug => "DEBUG",
+            Self::Neutral => "",
+        })
+    }
+}
+
+impl Display for Message {
+    /// The message without escape codes, e.g. `ERROR: [youtube] abc: Private video`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.r#type {
+            MessageType::Neutral => {}
+            MessageType::Debug if self.tag == "debug" => {}
+            r#type => write!(f, "{type}: ")?,
+        }
+        if !self.tag.is_empty() {
+            write!(f, "[{}] ", self.tag)?;
+        }
+        f.write_str(&self.content)
+    }
+}
+
+/// Remove ANSI escape codes and keep the last redraw of a line, the text after the last `\r`
+pub(crate) fn clean_line(s: &str) -> String {
+    let mut cleaned = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            cleaned.push(c);
+            continue;
         }
+
+        // CSI sequences such as `\x1b[0;31m` end with a byte in `@..=~`, other escape
+        // sequences are two characters long
+        if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+
+    let cleaned = cleaned.trim_end_matches(['\r', '\n']);
+    match cleaned
+        .rsplit('\r')
+        .find(|redraw| !redraw.trim().is_empty())
+    {
+        Some(redraw) => redraw.to_string(),
+        None => String::new(),
     }
 }
@@ -1,6 +1,19 @@
+mod debug_header;
+mod format;
+mod info;
+mod lifecycle_event;
 mod message;
 mod output;
 mod progress;
+mod ytdlp_error;
+mod ytdlp_warning;
 
+pub use debug_header::*;
+pub use format::*;
+pub use info::*;
+pub use lifecycle_event::*;
 pub use message::*;
+pub use output::*;
 pub use progress::*;
+pub use ytdlp_error::*;
+pub use ytdlp_warning::*;
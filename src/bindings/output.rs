@@ -2,10 +2,11 @@ use std::str::FromStr;
 
 use crate::ParseError;
 
-use super::{Message, Progress};
+use super::{message::clean_line, Message, Progress, PROGRESS_PREFIX};
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum Output {
-    Progress(Progress),
+    Progress(Box<Progress>),
     Message(Message),
 }
 
@@ -13,10 +14,27 @@ impl FromStr for Output {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.chars().next().is_some_and(|c| c == '{') {
-            todo!("handle json")
+        let cleaned = clean_line(s);
+
+        if let Some(json) = cleaned.strip_prefix(PROGRESS_PREFIX) {
+            Ok(Self::Progress(Box::new(
+                Progress::from_str(json).map_err(|e| e.replace(s.to_string()))?,
+            )))
+        } else if is_progress_object(&cleaned) {
+            Ok(Self::Progress(Box::new(
+                Progress::from_str(&cleaned).map_err(|e| e.replace(s.to_string()))?,
+            )))
         } else {
             Ok(Self::Message(Message::from_str(s)?))
         }
     }
 }
+
+/// Progress printed with a plain `%(progress)j` template, without [`PROGRESS_PREFIX`]
+///
+/// Other JSON such as the output of `--dump-json` has no `status` key and is kept as a message.
+fn is_progress_object(s: &str) -> bool {
+    s.starts_with('{')
+        && serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(s)
+            .is_ok_and(|object| object.contains_key("status"))
+}
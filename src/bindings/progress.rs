@@ -1,39 +1,112 @@
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::{ParseError, ParseErrorVariant};
+
+/// Marker put in front of progress lines by
+/// [`Builder::structured_progress`](crate::Builder::structured_progress), so they cannot be
+/// confused with other JSON printed by yt-dlp
+pub const PROGRESS_PREFIX: &str = "yt-dlp-rs:progress:";
+
+/// A progress line printed by yt-dlp with `--progress-template "%(progress)j"`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
 pub enum Progress {
     Finished {
-        downloaded_bytes: u64,
-        total_bytes: u64,
+        downloaded_bytes: Option<u64>,
+        total_bytes: Option<u64>,
         filename: String,
-        status: ProgressStatus,
-        elapsed: f64,
+        elapsed: Option<f64>,
         ctx_id: Option<u32>,
-        speed: f64,
+        speed: Option<f64>,
 
+        #[serde(rename = "_speed_str")]
         speed_str: String,
+        #[serde(rename = "_total_bytes_str")]
         total_bytes_str: String,
+        #[serde(rename = "_elapsed_str")]
         elapsed_str: String,
+        #[serde(rename = "_percent_str")]
         percent_str: String,
+        #[serde(rename = "_default_template")]
         default_template: String,
     },
     Downloading {
         downloaded_bytes: u64,
-        total_bytes: u64,
-        tempfilename: String,
+        total_bytes: Option<u64>,
+        total_bytes_estimate: Option<f64>,
+        #[serde(rename = "tmpfilename")]
+        tempfilename: Option<String>,
         filename: String,
         eta: Option<u64>,
         speed: Option<f64>,
         elapsed: Option<f64>,
         ctx_id: Option<u32>,
+        fragment_index: Option<u64>,
+        fragment_count: Option<u64>,
 
+        #[serde(rename = "_eta_str")]
         eta_str: String,
+        #[serde(rename = "_speed_str")]
         speed_str: String,
+        #[serde(rename = "_percent_str")]
         percent_str: String,
+        #[serde(rename = "_total_bytes_str")]
         total_bytes_str: String,
+        #[serde(rename = "_total_bytes_estimate_str")]
         total_bytes_estimate_str: String,
+        #[serde(rename = "_downloaded_bytes_str")]
         downloaded_bytes_str: String,
+        #[serde(rename = "_elapsed_str")]
         elapsed_str: String,
+        #[serde(rename = "_default_template")]
         default_template: String,
     }, // there is also "failed", but I cannot reproduce it
        // and there are no docs
 }
 
-pub enum ProgressStatus {}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressStatus {
+    Downloading,
+    Finished,
+}
+
+impl Progress {
+    pub fn status(&self) -> ProgressStatus {
+        match self {
+            Self::Downloading { .. } => ProgressStatus::Downloading,
+            Self::Finished { .. } => ProgressStatus::Finished,
+        }
+    }
+
+    /// Fraction of the file downloaded, between 0 and 1, if the total size is known or estimated
+    pub fn fraction(&self) -> Option<f64> {
+        match self {
+            Self::Finished { .. } => Some(1.),
+            Self::Downloading {
+                downloaded_bytes,
+                total_bytes,
+                total_bytes_estimate,
+                ..
+            } => total_bytes
+                .map(|total| total as f64)
+                .or(*total_bytes_estimate)
+                .filter(|total| *total > 0.)
+                .map(|total| (*downloaded_bytes as f64 / total).min(1.)),
+        }
+    }
+}
+
+impl FromStr for Progress {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map_err(|e| {
+            ParseError::new(
+                s.to_string(),
+                ParseErrorVariant::InvalidProgress(e.to_string()),
+            )
+        })
+    }
+}
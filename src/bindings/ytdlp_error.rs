@@ -0,0 +1,183 @@
+use std::{error::Error, fmt::Display};
+
+use crate::Message;
+
+/// An `ERROR:` message from yt-dlp, classified by its text
+///
+/// The classification matches the wording of yt-dlp and its YouTube extractor, messages that
+/// are not recognised are kept as [`YtDlpError::Unknown`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum YtDlpError {
+    /// `Video unavailable` and similar, e.g. removed videos or terminated accounts
+    VideoUnavailable {
+        video_id: Option<String>,
+        reason: String,
+    },
+    /// `Private video`
+    PrivateVideo {
+        video_id: Option<String>,
+    },
+    /// `Sign in to confirm your age`
+    AgeRestricted {
+        video_id: Option<String>,
+    },
+    /// Not available from the current location, `countries` are the ISO 3166 codes the video is
+    /// available in, if yt-dlp reports them
+    GeoRestricted {
+        video_id: Option<String>,
+        countries: Vec<String>,
+    },
+    /// `HTTP Error 403: Forbidden` and other HTTP errors
+    Http {
+        video_id: Option<String>,
+        status: u16,
+        reason: String,
+    },
+    /// `Requested format is not available`
+    FormatUnavailable {
+        video_id: Option<String>,
+    },
+    Unknown(Message),
+}
+
+impl YtDlpError {
+    /// ID of the video the error is about, if yt-dlp reported it
+    pub fn video_id(&self) -> Option<&str> {
+        match self {
+            Self::VideoUnavailable { video_id, .. }
+            | Self::PrivateVideo { video_id }
+            | Self::AgeRestricted { video_id }
+            | Self::GeoRestricted { video_id, .. }
+            | Self::Http { video_id, .. }
+            | Self::FormatUnavailable { video_id } => video_id.as_deref(),
+            Self::Unknown(_) => None,
+        }
+    }
+
+    /// Whether running yt-dlp again later may succeed: timeouts, rate limiting (HTTP 408 and
+    /// 429) and server errors (HTTP 5xx)
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::Http {
+                status: 408 | 429 | 500..=599,
+                ..
+            }
+        )
+    }
+}
+
+impl From<&Message> for YtDlpError {
+    fn from(message: &Message) -> Self {
+        let (video_id, text) = split_video_id(message);
+        let lowercase = text.to_ascii_lowercase();
+
+        if let Some((status, reason)) = http_error(text) {
+            return Self::Http {
+                video_id,
+                status,
+                reason,
+            };
+        }
+
+        if lowercase.starts_with("private video") {
+            Self::PrivateVideo { video_id }
+        } else if lowercase.contains("confirm your age") || lowercase.contains("age-restricted") {
+            Self::AgeRestricted { video_id }
+        } else if lowercase.contains("geo restriction")
+            || lowercase.contains("in your country")
+            || lowercase.contains("from your location")
+        {
+            Self::GeoRestricted {
+                video_id,
+                countries: countries(text),
+            }
+        } else if lowercase.starts_with("requested format is not available") {
+            Self::FormatUnavailable { video_id }
+        } else if lowercase.starts_with("video unavailable")
+            || lowercase.contains("video is no longer available")
+            || lowercase.contains("video has been removed")
+        {
+            Self::VideoUnavailable {
+                video_id,
+                reason: text.to_string(),
+            }
+        } else {
+            Self::Unknown(message.clone())
+        }
+    }
+}
+
+impl From<Message> for YtDlpError {
+    fn from(message: Message) -> Self {
+        Self::from(&message)
+    }
+}
+
+/// Extractors prefix messages with the ID of the video, e.g. `[youtube] ID: message`
+pub(crate) fn split_video_id(message: &Message) -> (Option<String>, &str) {
+    let content = message.content().trim();
+
+    match content.split_once(": ") {
+        Some((id, text)) if !message.tag().is_empty() && !id.contains(char::is_whitespace) => {
+            (Some(id.to_string()), text)
+        }
+        _ => (None, content),
+    }
+}
+
+/// Status and reason of `HTTP Error 429: Too Many Requests` anywhere in `text`
+pub(crate) fn http_error(text: &str) -> Option<(u16, String)> {
+    let http = text.to_ascii_lowercase().find("http error ")?;
+    let status = &text[http + "http error ".len()..];
+    let digits = status.bytes().take_while(u8::is_ascii_digit).count();
+    let status = status[..digits].parse().ok()?;
+
+    let reason = text[http..]
+        .split_once(": ")
+        .map(|(_, reason)| reason.split(" (caused by").next().unwrap_or(reason))
+        .unwrap_or_default()
+        .trim_end_matches('.')
+        .to_string();
+
+    Some((status, reason))
+}
+
+/// Country codes listed after "available in", e.g. `only available in US, CA and GB`
+fn countries(text: &str) -> Vec<String> {
+    let Some((_, list)) = text.split_once("available in ") else {
+        return Vec::new();
+    };
+
+    list.split([',', ' '])
+        .filter(|word| !word.is_empty() && *word != "and")
+        .map(|word| word.trim_end_matches('.'))
+        .take_while(|word| word.len() == 2 && word.bytes().all(|c| c.is_ascii_uppercase()))
+        .map(str::to_string)
+        .collect()
+}
+
+impl Display for YtDlpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(id) = self.video_id() {
+            write!(f, "{id}: ")?;
+        }
+
+        match self {
+            Self::VideoUnavailable { reason, .. } => f.write_str(reason),
+            Self::PrivateVideo { .. } => f.write_str("private video"),
+            Self::AgeRestricted { .. } => f.write_str("age restricted video"),
+            Self::GeoRestricted { countries, .. } if countries.is_empty() => {
+                f.write_str("video is not available from this location")
+            }
+            Self::GeoRestricted { countries, .. } => {
+                write!(f, "video is only available in {}", countries.join(", "))
+            }
+            Self::Http { status, reason, .. } => write!(f, "HTTP error {status}: {reason}"),
+            Self::FormatUnavailable { .. } => f.write_str("requested format is not available"),
+            Self::Unknown(message) => f.write_str(message.content().trim()),
+        }
+    }
+}
+
+impl Error for YtDlpError {}
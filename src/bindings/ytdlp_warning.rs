@@ -0,0 +1,161 @@
+use std::fmt::Display;
+
+use crate::Message;
+
+use super::ytdlp_error::{http_error, split_video_id};
+
+/// A `WARNING:` message from yt-dlp, classified by its text
+///
+/// Warnings often come before a hard failure, e.g. a failing nsig extraction usually means the
+/// YouTube extractor needs an update. Messages that are not recognised are kept as
+/// [`YtDlpWarning::Unknown`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum YtDlpWarning {
+    /// `Falling back on generic information extractor`, no extractor supports the URL
+    GenericExtractorFallback,
+    /// `nsig extraction failed`, some YouTube formats will be missing, `details` is the text
+    /// after the colon, e.g. `Some formats may be missing`
+    NsigExtractionFailed {
+        video_id: Option<String>,
+        details: String,
+    },
+    /// `Some formats are possibly damaged`, they are deprioritised
+    DamagedFormats {
+        video_id: Option<String>,
+    },
+    /// `Unable to download webpage`, with the HTTP status if the request failed with one
+    WebpageUnavailable {
+        video_id: Option<String>,
+        status: Option<u16>,
+        reason: String,
+    },
+    /// `ffmpeg not found` or `ffmpeg is not installed`, formats cannot be merged or converted
+    FfmpegNotFound,
+    Unknown(Message),
+}
+
+/// How much a [`YtDlpWarning`] affects the download, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WarningSeverity {
+    /// Nothing to act on
+    Info,
+    /// The download goes on, but some formats or metadata may be missing
+    Degraded,
+    /// Downloads are likely to fail or be of lower quality until the cause is fixed, e.g.
+    /// rate limiting or a missing dependency
+    Critical,
+}
+
+impl YtDlpWarning {
+    /// ID of the video the warning is about, if yt-dlp reported it
+    pub fn video_id(&self) -> Option<&str> {
+        match self {
+            Self::NsigExtractionFailed { video_id, .. }
+            | Self::DamagedFormats { video_id }
+            | Self::WebpageUnavailable { video_id, .. } => video_id.as_deref(),
+            Self::GenericExtractorFallback | Self::FfmpegNotFound | Self::Unknown(_) => None,
+        }
+    }
+
+    /// How much the warning affects the download, a best guess from its kind:
+    /// - [`Critical`](WarningSeverity::Critical) for a missing ffmpeg and webpages refused with
+    ///   HTTP 403 or 429, which usually mean blocking or rate limiting for every request that
+    ///   follows
+    /// - [`Degraded`](WarningSeverity::Degraded) for the generic extractor fallback, a failed
+    ///   nsig extraction and webpages that failed for any other reason
+    /// - [`Info`](WarningSeverity::Info) for damaged formats, which yt-dlp already deprioritises,
+    ///   and [`Unknown`](Self::Unknown) warnings, since most of them are harmless
+    pub fn severity(&self) -> WarningSeverity {
+        match self {
+            Self::DamagedFormats { .. } | Self::Unknown(_) => WarningSeverity::Info,
+            Self::WebpageUnavailable {
+                status: Some(403 | 429),
+                ..
+            }
+            | Self::FfmpegNotFound => WarningSeverity::Critical,
+            Self::GenericExtractorFallback
+            | Self::NsigExtractionFailed { .. }
+            | Self::WebpageUnavailable { .. } => WarningSeverity::Degraded,
+        }
+    }
+}
+
+impl From<&Message> for YtDlpWarning {
+    fn from(message: &Message) -> Self {
+        let (video_id, text) = split_video_id(message);
+        let lowercase = text.to_ascii_lowercase();
+
+        if lowercase.starts_with("falling back on generic information extractor") {
+            Self::GenericExtractorFallback
+        } else if let Some(start) = lowercase.find("nsig extraction failed") {
+            let details = &text[start + "nsig extraction failed".len()..];
+            Self::NsigExtractionFailed {
+                video_id,
+                details: details.trim_start_matches(':').trim().to_string(),
+            }
+        } else if lowercase.starts_with("some formats are possibly damaged") {
+            Self::DamagedFormats { video_id }
+        } else if lowercase.starts_with("unable to download webpage") {
+            let reason = &text["unable to download webpage".len()..];
+            let (status, reason) = match http_error(reason) {
+                Some((status, reason)) => (Some(status), reason),
+                None => {
+                    let reason = reason.trim_start_matches(':').trim();
+                    let reason = reason.split(" (caused by").next().unwrap_or(reason);
+                    (None, reason.to_string())
+                }
+            };
+            Self::WebpageUnavailable {
+                video_id,
+                status,
+                reason,
+            }
+        } else if lowercase.starts_with("ffmpeg not found")
+            || lowercase.contains("ffmpeg is not installed")
+        {
+            Self::FfmpegNotFound
+        } else {
+            Self::Unknown(message.clone())
+        }
+    }
+}
+
+impl From<Message> for YtDlpWarning {
+    fn from(message: Message) -> Self {
+        Self::from(&message)
+    }
+}
+
+impl Display for YtDlpWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(id) = self.video_id() {
+            write!(f, "{id}: ")?;
+        }
+
+        match self {
+            Self::GenericExtractorFallback => {
+                f.write_str("falling back on generic information extractor")
+            }
+            Self::NsigExtractionFailed { details, .. } if details.is_empty() => {
+                f.write_str("nsig extraction failed")
+            }
+            Self::NsigExtractionFailed { details, .. } => {
+                write!(f, "nsig extraction failed: {details}")
+            }
+            Self::DamagedFormats { .. } => f.write_str("some formats are possibly damaged"),
+            Self::WebpageUnavailable {
+                status: Some(status),
+                reason,
+                ..
+            } => write!(
+                f,
+                "unable to download webpage: HTTP error {status}: {reason}"
+            ),
+            Self::WebpageUnavailable { reason, .. } => {
+                write!(f, "unable to download webpage: {reason}")
+            }
+            Self::FfmpegNotFound => f.write_str("ffmpeg not found"),
+            Self::Unknown(message) => f.write_str(message.content().trim()),
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{Builder, ParseError, ParseErrorVariant};
+
+/// Settings for converting downloads to audio-only files, applied with
+/// [`Builder::audio_extraction`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AudioExtraction {
+    /// `--audio-format`, yt-dlp uses [`AudioFormat::Best`] if `None`
+    pub formats: Option<AudioFormats>,
+    /// `--audio-quality`, yt-dlp uses VBR level 5 if `None`
+    pub quality: Option<AudioQuality>,
+    /// `--keep-video`, keep the original file after extracting the audio
+    pub keep_video: bool,
+}
+
+/// Formats supported by `--audio-format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioFormat {
+    /// `best`, keep the original audio codec if possible
+    Best,
+    /// `aac`
+    Aac,
+    /// `alac`
+    Alac,
+    /// `flac`
+    Flac,
+    /// `m4a`
+    M4a,
+    /// `mp3`
+    Mp3,
+    /// `opus`
+    Opus,
+    /// `vorbis`
+    Vorbis,
+    /// `wav`
+    Wav,
+}
+
+/// `/` separated rules for `--audio-format`, e.g. `aac>m4a/mp3`, the first rule that applies
+/// to the downloaded audio is used
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AudioFormats(Vec<AudioFormatRule>);
+
+/// One rule of [`AudioFormats`], e.g. `aac>m4a` or `mp3`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioFormatRule {
+    /// Only apply the rule to audio with this extension, the part before `>`
+    pub source: Option<String>,
+    pub target: AudioFormat,
+}
+
+/// Quality for `--audio-quality`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioQuality {
+    /// VBR level from 0 (best) to 10 (worst)
+    Vbr(u8),
+    /// Bitrate in kbit/s, e.g. `192K`, yt-dlp takes values up to 10 as VBR levels
+    Bitrate(u32),
+}
+
+impl AudioExtraction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn format(mut self, formats: impl Into<AudioFormats>) -> Self {
+        self.formats = Some(formats.into());
+        self
+    }
+
+    pub fn quality(mut self, quality: AudioQuality) -> Self {
+        self.quality = Some(quality);
+        self
+    }
+
+    pub fn keep_video(mut self) -> Self {
+        self.keep_video = true;
+        self
+    }
+}
+
+impl AudioFormat {
+    const ALL: [Self; 9] = [
+        Self::Best,
+        Self::Aac,
+        Self::Alac,
+        Self::Flac,
+        Self::M4a,
+        Self::Mp3,
+        Self::Opus,
+        Self::Vorbis,
+        Self::Wav,
+    ];
+
+    /// Name of the format as used by yt-dlp
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Best => "best",
+            Self::Aac => "aac",
+            Self::Alac => "alac",
+            Self::Flac => "flac",
+            Self::M4a => "m4a",
+            Self::Mp3 => "mp3",
+            Self::Opus => "opus",
+            Self::Vorbis => "vorbis",
+            Self::Wav => "wav",
+        }
+    }
+}
+
+impl AudioFormats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a rule, used if none of the previous rules apply
+    pub fn then(mut self, rule: impl Into<AudioFormatRule>) -> Self {
+        self.0.push(rule.into());
+        self
+    }
+
+    pub fn rules(&self) -> &[AudioFormatRule] {
+        &self.0
+    }
+}
+
+impl AudioFormatRule {
+    pub fn new(target: AudioFormat) -> Self {
+        Self {
+            source: None,
+            target,
+        }
+    }
+
+    /// Only apply the rule to audio with the extension `source`, e.g. `aac>m4a`
+    pub fn source(mut self, source: &str) -> Self {
+        self.source = Some(source.to_string());
+        self
+    }
+}
+
+impl AudioQuality {
+    /// A VBR level, `None` if it is not between 0 and 10
+    pub fn vbr(level: u8) -> Option<Self> {
+        (level <= 10).then_some(Self::Vbr(level))
+    }
+
+    /// A bitrate in kbit/s, `None` if yt-dlp would take it as a VBR level instead
+    pub fn bitrate(kbps: u32) -> Option<Self> {
+        (kbps > 10).then_some(Self::Bitrate(kbps))
+    }
+}
+
+impl From<AudioFormat> for AudioFormatRule {
+    fn from(target: AudioFormat) -> Self {
+        Self::new(target)
+    }
+}
+
+impl From<AudioFormat> for AudioFormats {
+    fn from(target: AudioFormat) -> Self {
+        Self::new().then(target)
+    }
+}
+
+impl From<Vec<AudioFormatRule>> for AudioFormats {
+    fn from(rules: Vec<AudioFormatRule>) -> Self {
+        Self(rules)
+    }
+}
+
+impl From<AudioFormat> for String {
+    fn from(format: AudioFormat) -> Self {
+        format.name().to_string()
+    }
+}
+
+impl From<AudioFormats> for String {
+    fn from(formats: AudioFormats) -> Self {
+        formats.to_string()
+    }
+}
+
+impl From<&AudioFormats> for String {
+    fn from(formats: &AudioFormats) -> Self {
+        formats.to_string()
+    }
+}
+
+impl From<AudioQuality> for String {
+    fn from(quality: AudioQuality) -> Self {
+        quality.to_string()
+    }
+}
+
+impl Display for AudioFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Display for AudioFormats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, rule) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str("/")?;
+            }
+            write!(f, "{rule}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for AudioFormatRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(source) = &self.source {
+            write!(f, "{source}>")?;
+        }
+        f.write_str(self.target.name())
+    }
+}
+
+impl Display for AudioQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Vbr(level) => write!(f, "{level}"),
+            Self::Bitrate(kbps) => write!(f, "{kbps}K"),
+        }
+    }
+}
+
+impl FromStr for AudioFormat {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                ParseError::new(
+                    s.to_string(),
+                    ParseErrorVariant::UnknownAudioFormat(s.to_string()),
+                )
+            })
+    }
+}
+
+impl FromStr for AudioFormats {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split('/')
+            .map(|rule| AudioFormatRule::from_str(rule).map_err(|e| e.replace(s.to_string())))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+impl FromStr for AudioFormatRule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (source, target) = match s.split_once('>') {
+            Some((source, target)) => {
+                let source = source.trim();
+                if source.is_empty() || !source.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    return Err(ParseError::new(
+                        s.to_string(),
+                        ParseErrorVariant::UnknownAudioFormat(source.to_string()),
+                    ));
+                }
+                (Some(source.to_lowercase()), target)
+            }
+            None => (None, s),
+        };
+
+        Ok(Self {
+            source,
+            target: AudioFormat::from_str(target.trim()).map_err(|e| e.replace(s.to_string()))?,
+        })
+    }
+}
+
+impl FromStr for AudioQuality {
+    type Err = ParseError;
+
+    /// Parses a VBR level or a bitrate, with or without the `K` suffix, like yt-dlp
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = s.trim_end_matches(['k', 'K']);
+        let quality = match number.parse::<u32>() {
+            Ok(level @ 0..=10) => Some(Self::Vbr(level as u8)),
+            Ok(kbps) => Some(Self::Bitrate(kbps)),
+            Err(_) => None,
+        };
+
+        quality
+            .ok_or_else(|| ParseError::new(s.to_string(), ParseErrorVariant::InvalidAudioQuality))
+    }
+}
+
+impl Builder {
+    /// Pass `--extract-audio` and the other flags set in `extraction`
+    pub fn audio_extraction(&mut self, extraction: &AudioExtraction) -> &mut Self {
+        self.extract_audio();
+        if let Some(formats) = &extraction.formats {
+            self.audio_format(formats);
+        }
+        if let Some(quality) = extraction.quality {
+            self.audio_quality(quality);
+        }
+        if extraction.keep_video {
+            self.keep_video();
+        }
+        self
+    }
+}
@@ -0,0 +1,124 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{ParseError, ParseErrorVariant};
+
+/// Suffixes understood by yt-dlp, each one 1024 times the previous
+const SUFFIXES: [char; 8] = ['K', 'M', 'G', 'T', 'P', 'E', 'Z', 'Y'];
+
+/// A size or rate in bytes, e.g. for [`Builder::limit_rate`](crate::Builder::limit_rate)
+///
+/// Rendered with the largest binary suffix that divides it exactly, so `ByteSize::mib(10)`
+/// is passed as `10M`. Note that yt-dlp treats `K`, `M` and `G` as powers of 1024 and does not
+/// accept a trailing `B`. Strings in yt-dlp's format such as `50k` are converted with
+/// `.parse()` or [`TryFrom`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteSize(u64);
+
+impl ByteSize {
+    pub fn new(bytes: u64) -> Self {
+        Self(bytes)
+    }
+
+    /// Saturates at `u64::MAX` bytes instead of overflowing, as do [`mib`](Self::mib) and
+    /// [`gib`](Self::gib)
+    pub fn kib(kib: u64) -> Self {
+        Self(kib.saturating_mul(1 << 10))
+    }
+
+    pub fn mib(mib: u64) -> Self {
+        Self(mib.saturating_mul(1 << 20))
+    }
+
+    pub fn gib(gib: u64) -> Self {
+        Self(gib.saturating_mul(1 << 30))
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(bytes: u64) -> Self {
+        Self(bytes)
+    }
+}
+
+impl TryFrom<&str> for ByteSize {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s)
+    }
+}
+
+impl From<ByteSize> for String {
+    fn from(size: ByteSize) -> Self {
+        size.to_string()
+    }
+}
+
+impl From<&ByteSize> for String {
+    fn from(size: &ByteSize) -> Self {
+        size.to_string()
+    }
+}
+
+impl Display for ByteSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut value = self.0;
+        let mut suffix = None;
+
+        for c in SUFFIXES {
+            if value == 0 || !value.is_multiple_of(1024) {
+                break;
+            }
+            value /= 1024;
+            suffix = Some(c);
+        }
+
+        match suffix {
+            Some(suffix) => write!(f, "{value}{suffix}"),
+            None => write!(f, "{value}"),
+        }
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = ParseError;
+
+    /// Parses sizes the way yt-dlp does, e.g. `1024`, `50K` or `4.2M`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseError::new(s.to_string(), ParseErrorVariant::InvalidByteSize);
+
+        let (number, exponent) = match s.chars().last() {
+            Some(c) if c.is_ascii_alphabetic() => {
+                let exponent = SUFFIXES
+                    .iter()
+                    .position(|suffix| suffix.eq_ignore_ascii_case(&c))
+                    .ok_or_else(error)?;
+                (&s[..s.len() - 1], exponent as i32 + 1)
+            }
+            _ => (s, 0),
+        };
+
+        // `f64::from_str` also accepts forms such as `1e3` and `inf`, which yt-dlp does not
+        let mut parts = number.splitn(2, '.');
+        let valid = parts
+            .next()
+            .is_some_and(|part| !part.is_empty() && part.bytes().all(|c| c.is_ascii_digit()))
+            && parts
+                .next()
+                .is_none_or(|part| !part.is_empty() && part.bytes().all(|c| c.is_ascii_digit()));
+        if !valid {
+            return Err(error());
+        }
+
+        let bytes = (number.parse::<f64>().map_err(|_| error())? * 1024f64.powi(exponent)).round();
+        if bytes > u64::MAX as f64 {
+            return Err(error());
+        }
+
+        Ok(Self(bytes as u64))
+    }
+}
@@ -0,0 +1,276 @@
+use std::{
+    fmt::Display,
+    ops::{Range, RangeFrom, RangeTo},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::{ParseError, ParseErrorVariant};
+
+/// A part of a video to download, for [`Builder::download_sections`](crate::Builder::download_sections)
+///
+/// To download several sections, call `download_sections` once for each of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DownloadSection {
+    /// `*START-END`, from the start of the video or until its end if `None`
+    TimeRange {
+        start: Option<SectionTime>,
+        end: Option<SectionTime>,
+    },
+    /// Chapters with a title matching the regular expression, a leading `*` is escaped as `\*`
+    /// when rendered and unescaped when parsed
+    Chapters(String),
+    /// `*from-url`, between the `start_time` and `end_time` extracted from the URL
+    FromUrl,
+}
+
+/// A timestamp in a [`DownloadSection::TimeRange`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectionTime {
+    FromStart(Duration),
+    /// Time before the end of the video, a negative timestamp
+    FromEnd(Duration),
+}
+
+impl DownloadSection {
+    pub fn time_range(start: Option<SectionTime>, end: Option<SectionTime>) -> Self {
+        Self::TimeRange { start, end }
+    }
+
+    pub fn chapters(regex: &str) -> Self {
+        Self::Chapters(regex.to_string())
+    }
+}
+
+impl From<Duration> for SectionTime {
+    fn from(time: Duration) -> Self {
+        Self::FromStart(time)
+    }
+}
+
+impl From<Range<Duration>> for DownloadSection {
+    fn from(range: Range<Duration>) -> Self {
+        Self::time_range(Some(range.start.into()), Some(range.end.into()))
+    }
+}
+
+impl From<RangeFrom<Duration>> for DownloadSection {
+    fn from(range: RangeFrom<Duration>) -> Self {
+        Self::time_range(Some(range.start.into()), None)
+    }
+}
+
+impl From<RangeTo<Duration>> for DownloadSection {
+    fn from(range: RangeTo<Duration>) -> Self {
+        Self::time_range(None, Some(range.end.into()))
+    }
+}
+
+impl From<DownloadSection> for String {
+    fn from(section: DownloadSection) -> Self {
+        section.to_string()
+    }
+}
+
+impl From<&DownloadSection> for String {
+    fn from(section: &DownloadSection) -> Self {
+        section.to_string()
+    }
+}
+
+impl Display for DownloadSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TimeRange { start, end } => {
+                f.write_str("*")?;
+                if let Some(start) = start {
+                    write!(f, "{start}")?;
+                }
+                match end {
+                    Some(end) => write!(f, "-{end}"),
+                    // an open end has to be written out, yt-dlp rejects `*-`
+                    None => f.write_str("-inf"),
+                }
+            }
+            // a leading `*` would make yt-dlp read the regex as a time range
+            Self::Chapters(regex) => match regex.strip_prefix('*') {
+                Some(regex) => write!(f, "\\*{regex}"),
+                None => f.write_str(regex),
+            },
+            Self::FromUrl => f.write_str("*from-url"),
+        }
+    }
+}
+
+impl Display for SectionTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FromStart(time) => write!(f, "{}", time.as_secs_f64()),
+            Self::FromEnd(time) => write!(f, "-{}", time.as_secs_f64()),
+        }
+    }
+}
+
+impl FromStr for DownloadSection {
+    type Err = ParseError;
+
+    /// Parses a section the way yt-dlp does, except that several comma separated time ranges
+    /// in one section such as `*0-10,20-30` are rejected
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseError::new(s.to_string(), ParseErrorVariant::InvalidTimeRange);
+
+        let Some(range) = s.strip_prefix('*') else {
+            // undo the escape of a leading `*` written by `Display`
+            return Ok(Self::chapters(match s.strip_prefix('\\') {
+                Some(regex) if regex.starts_with('*') => regex,
+                _ => s,
+            }));
+        };
+        if range == "from-url" {
+            return Ok(Self::FromUrl);
+        }
+
+        let range = range.trim();
+        // a leading `-` is either the sign of the start or the separator if there is no start
+        let (start, end) = match range.strip_prefix('-') {
+            Some(rest) => match rest.trim_start().starts_with('-') || !rest.contains('-') {
+                true => ("", rest),
+                false => {
+                    let (start, end) = rest.split_once('-').ok_or_else(error)?;
+                    (&range[..start.len() + 1], end)
+                }
+            },
+            None => range.split_once('-').ok_or_else(error)?,
+        };
+
+        let time = |s: &str| {
+            let (from_end, s) = match s.trim().strip_prefix('-') {
+                Some(s) => (true, s),
+                None => (false, s.trim()),
+            };
+            let time = parse_timestamp(s).ok_or_else(error)?;
+            Ok(match from_end {
+                true => SectionTime::FromEnd(time),
+                false => SectionTime::FromStart(time),
+            })
+        };
+
+        let start = match start.trim() {
+            "" => None,
+            start => Some(time(start)?),
+        };
+        let end = match end.trim() {
+            "" if start.is_none() => return Err(error()),
+            "" | "inf" | "infinite" => None,
+            end => Some(time(end)?),
+        };
+
+        Ok(Self::TimeRange { start, end })
+    }
+}
+
+/// A duration the way yt-dlp's `parse_duration` reads it, e.g. `1:30:00`, `1h30m`, `90s` or
+/// `PT1H30M`, fractions are also accepted for hours and minutes
+fn parse_timestamp(s: &str) -> Option<Duration> {
+    let s = s.strip_suffix(['Z', 'z']).unwrap_or(s);
+    parse_clock(s)
+        .or_else(|| parse_units(s))
+        .map(Duration::from_secs_f64)
+}
+
+/// `[[[DD:]HH:]MM:]SS[.fff]`, the seconds have at most 2 digits if there are other parts
+fn parse_clock(s: &str) -> Option<f64> {
+    let parts = s.split(':').collect::<Vec<_>>();
+    let (seconds, rest) = parts.split_last()?;
+    if rest.len() > 3 || !rest.iter().all(|part| is_number(part, false)) {
+        return None;
+    }
+
+    let (whole, _) = seconds.split_once('.').unwrap_or((seconds, ""));
+    if !is_number(seconds, true) || (!rest.is_empty() && whole.len() > 2) {
+        return None;
+    }
+
+    let seconds = seconds.parse::<f64>().ok()?;
+    rest.iter()
+        .rev()
+        .zip([60., 3600., 86400.])
+        .try_fold(seconds, |total, (part, scale)| {
+            part.parse::<f64>().ok().map(|part| total + part * scale)
+        })
+}
+
+/// `[P[DDd]T][HHh][MMm][SS[.fff]s]` with optional long unit names, e.g. `1 hour, 30 mins`
+fn parse_units(s: &str) -> Option<f64> {
+    const UNITS: [(&[&str], f64); 3] = [
+        (&["h", "hr", "hrs", "hour", "hours"], 3600.),
+        (&["m", "min", "mins", "minute", "minutes"], 60.),
+        (&["s", "sec", "secs", "second", "seconds"], 1.),
+    ];
+
+    let s = s.to_ascii_lowercase();
+    let mut total = 0.;
+    let mut rest = s.trim();
+
+    // the days of an ISO 8601 duration, e.g. `P1DT2H`
+    let iso = rest.strip_prefix('p').unwrap_or(rest).split_once('t');
+    if let Some((days, time)) = iso {
+        let days = days.trim().trim_end_matches(',');
+        let days = ["days", "day", "d"]
+            .into_iter()
+            .find_map(|unit| days.strip_suffix(unit))
+            .map(str::trim_end)
+            .or(days.is_empty().then_some("0"));
+        if let Some(days) = days.filter(|days| is_number(days, false)) {
+            total += days.parse::<f64>().ok()? * 86400.;
+            rest = time;
+        }
+    }
+
+    let mut next = 0;
+    let mut found = false;
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+
+        let end = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let (number, after) = rest.split_at(end);
+        if !is_number(number, true) {
+            return None;
+        }
+
+        let after = after.trim_start();
+        let end = after
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(after.len());
+        let (unit, after) = after.split_at(end);
+        rest = after.trim_start().strip_prefix(',').unwrap_or(after);
+
+        // units have to come in order, `30m1h` is not a duration
+        let index = next
+            + UNITS[next..]
+                .iter()
+                .position(|(names, _)| names.contains(&unit))?;
+        total += number.parse::<f64>().ok()? * UNITS[index].1;
+        next = index + 1;
+        found = true;
+    }
+
+    found.then_some(total)
+}
+
+/// Digits, optionally with a fractional part
+fn is_number(s: &str, fraction: bool) -> bool {
+    let (whole, fractional) = match s.split_once('.') {
+        Some((whole, fractional)) if fraction => (whole, Some(fractional)),
+        Some(_) => return false,
+        None => (s, None),
+    };
+
+    let digits = |s: &str| !s.is_empty() && s.bytes().all(|c| c.is_ascii_digit());
+    digits(whole) && fractional.is_none_or(digits)
+}
@@ -0,0 +1,609 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{ParseError, ParseErrorVariant};
+
+use super::scanner::Scanner;
+
+/// A format selection expression, passed to [`Builder::format`](crate::Builder::format)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatSelector {
+    /// A single format, e.g. `bestvideo[ext=mp4]`
+    Format {
+        spec: FormatSpec,
+        filters: Vec<FormatFilter>,
+    },
+    /// A parenthesised selector with filters applied to all of it, e.g. `(mp4,webm)[height<480]`
+    Group {
+        selector: Box<FormatSelector>,
+        filters: Vec<FormatFilter>,
+    },
+    /// `a+b`, merge the selected formats into one file
+    Merge(Vec<FormatSelector>),
+    /// `a/b`, the first selector that matches any format
+    Fallback(Vec<FormatSelector>),
+    /// `a,b`, download every selected format
+    Multiple(Vec<FormatSelector>),
+}
+
+/// The format named by a [`FormatSelector::Format`], before filters are applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatSpec {
+    /// `best`, `bestvideo`, `bestaudio` etc., optionally the n-th best as in `best.2`
+    Best(Streams, Option<u32>),
+    /// `worst`, `worstvideo`, `worstaudio` etc., optionally the n-th worst as in `worst.2`
+    Worst(Streams, Option<u32>),
+    /// `all`, every format separately
+    All,
+    /// `mergeall`, every format merged together
+    MergeAll,
+    /// A format code or file extension, e.g. `137` or `mp4`
+    ///
+    /// Words one letter off a keyword, such as `bets`, are rejected as typos when parsing.
+    Id(String),
+}
+
+/// Which streams a format must contain to be picked by [`FormatSpec::Best`] or
+/// [`FormatSpec::Worst`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Streams {
+    /// `best`, formats with both video and audio
+    Both,
+    /// `best*`, any format
+    Any,
+    /// `bestvideo`, video-only formats
+    VideoOnly,
+    /// `bestvideo*`, formats containing video
+    Video,
+    /// `bestaudio`, audio-only formats
+    AudioOnly,
+    /// `bestaudio*`, formats containing audio
+    Audio,
+}
+
+/// A filter in square brackets, e.g. `[height<=?720]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatFilter {
+    pub key: String,
+    pub comparison: Comparison,
+    pub value: String,
+    /// Also match formats where the field is unknown, the `?` in `[height<=?720]`
+    pub none_inclusive: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+    /// `=`
+    Eq,
+    /// `!=`
+    Ne,
+    /// `^=`
+    StartsWith,
+    /// `!^=`
+    NotStartsWith,
+    /// `$=`
+    EndsWith,
+    /// `!$=`
+    NotEndsWith,
+    /// `*=`
+    Contains,
+    /// `!*=`
+    NotContains,
+    /// `~=`, the value is a regular expression
+    Matches,
+    /// `!~=`
+    NotMatches,
+}
+
+impl Comparison {
+    /// Longest operators first, so that prefixes such as `<` do not shadow `<=`
+    const ALL: [Self; 14] = [
+        Self::NotStartsWith,
+        Self::NotEndsWith,
+        Self::NotContains,
+        Self::NotMatches,
+        Self::Le,
+        Self::Ge,
+        Self::Ne,
+        Self::StartsWith,
+        Self::EndsWith,
+        Self::Contains,
+        Self::Matches,
+        Self::Lt,
+        Self::Gt,
+        Self::Eq,
+    ];
+
+    pub fn operator(&self) -> &'static str {
+        match self {
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Eq => "=",
+            Self::Ne => "!=",
+            Self::StartsWith => "^=",
+            Self::NotStartsWith => "!^=",
+            Self::EndsWith => "$=",
+            Self::NotEndsWith => "!$=",
+            Self::Contains => "*=",
+            Self::NotContains => "!*=",
+            Self::Matches => "~=",
+            Self::NotMatches => "!~=",
+        }
+    }
+}
+
+impl FormatSelector {
+    pub fn new(spec: FormatSpec) -> Self {
+        Self::Format {
+            spec,
+            filters: Vec::new(),
+        }
+    }
+
+    /// `best`
+    pub fn best() -> Self {
+        Self::new(FormatSpec::Best(Streams::Both, None))
+    }
+
+    /// `worst`
+    pub fn worst() -> Self {
+        Self::new(FormatSpec::Worst(Streams::Both, None))
+    }
+
+    /// `bestvideo`
+    pub fn best_video() -> Self {
+        Self::new(FormatSpec::Best(Streams::VideoOnly, None))
+    }
+
+    /// `worstvideo`
+    pub fn worst_video() -> Self {
+        Self::new(FormatSpec::Worst(Streams::VideoOnly, None))
+    }
+
+    /// `bestaudio`
+    pub fn best_audio() -> Self {
+        Self::new(FormatSpec::Best(Streams::AudioOnly, None))
+    }
+
+    /// `worstaudio`
+    pub fn worst_audio() -> Self {
+        Self::new(FormatSpec::Worst(Streams::AudioOnly, None))
+    }
+
+    /// A format code or file extension
+    pub fn id(id: &str) -> Self {
+        Self::new(FormatSpec::Id(id.to_string()))
+    }
+
+    /// Restrict the selected formats with a filter, grouping the selector if needed
+    pub fn filter(self, filter: FormatFilter) -> Self {
+        match self {
+            Self::Format { spec, mut filters } => {
+                filters.push(filter);
+                Self::Format { spec, filters }
+            }
+            Self::Group {
+                selector,
+                mut filters,
+            } => {
+                filters.push(filter);
+                Self::Group { selector, filters }
+            }
+            selector => Self::Group {
+                selector: Box::new(selector),
+                filters: vec![filter],
+            },
+        }
+    }
+
+    /// `self+other`
+    pub fn merge(self, other: Self) -> Self {
+        match self {
+            Self::Merge(mut selectors) => {
+                selectors.push(other);
+                Self::Merge(selectors)
+            }
+            selector => Self::Merge(vec![selector, other]),
+        }
+    }
+
+    /// `self/other`
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            Self::Fallback(mut selectors) => {
+                selectors.push(other);
+                Self::Fallback(selectors)
+            }
+            selector => Self::Fallback(vec![selector, other]),
+        }
+    }
+
+    /// `self,other`
+    pub fn and(self, other: Self) -> Self {
+        match self {
+            Self::Multiple(mut selectors) => {
+                selectors.push(other);
+                Self::Multiple(selectors)
+            }
+            selector => Self::Multiple(vec![selector, other]),
+        }
+    }
+
+    /// Binding strength of the operator, used to decide where parentheses are needed
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Multiple(_) => 0,
+            Self::Fallback(_) => 1,
+            Self::Merge(_) => 2,
+            Self::Format { .. } | Self::Group { .. } => 3,
+        }
+    }
+
+    fn fmt_joined(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        selectors: &[Self],
+        separator: &str,
+    ) -> std::fmt::Result {
+        for (i, selector) in selectors.iter().enumerate() {
+            if i != 0 {
+                f.write_str(separator)?;
+            }
+
+            if selector.precedence() <= self.precedence() {
+                write!(f, "({selector})")?;
+            } else {
+                write!(f, "{selector}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FormatFilter {
+    pub fn new(key: &str, comparison: Comparison, value: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            comparison,
+            value: value.to_string(),
+            none_inclusive: false,
+        }
+    }
+
+    /// Also match formats where the field is unknown
+    pub fn none_inclusive(mut self) -> Self {
+        self.none_inclusive = true;
+        self
+    }
+}
+
+impl Display for FormatSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let filters = match self {
+            Self::Format { spec, filters } => {
+                write!(f, "{spec}")?;
+                filters
+            }
+            Self::Group { selector, filters } => {
+                write!(f, "({selector})")?;
+                filters
+            }
+            Self::Merge(selectors) => return self.fmt_joined(f, selectors, "+"),
+            Self::Fallback(selectors) => return self.fmt_joined(f, selectors, "/"),
+            Self::Multiple(selectors) => return self.fmt_joined(f, selectors, ","),
+        };
+
+        filters.iter().try_for_each(|filter| write!(f, "{filter}"))
+    }
+}
+
+impl Display for FormatSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (quality, streams, nth) = match self {
+            Self::Best(streams, nth) => ("best", streams, nth),
+            Self::Worst(streams, nth) => ("worst", streams, nth),
+            Self::All => return f.write_str("all"),
+            Self::MergeAll => return f.write_str("mergeall"),
+            Self::Id(id) => return f.write_str(id),
+        };
+
+        let streams = match streams {
+            Streams::Both => "",
+            Streams::Any => "*",
+            Streams::VideoOnly => "video",
+            Streams::Video => "video*",
+            Streams::AudioOnly => "audio",
+            Streams::Audio => "audio*",
+        };
+
+        match nth {
+            Some(nth) => write!(f, "{quality}{streams}.{nth}"),
+            None => write!(f, "{quality}{streams}"),
+        }
+    }
+}
+
+impl Display for FormatFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}{}{}",
+            self.key,
+            self.comparison.operator(),
+            if self.none_inclusive { "?" } else { "" }
+        )?;
+
+        let needs_quotes = self.value.is_empty()
+            || self
+                .value
+                .contains(|c: char| c == ']' || c == '\'' || c == '"' || c.is_whitespace());
+        match needs_quotes {
+            true => write!(f, "'{}']", self.value.replace('\'', "\\'")),
+            false => write!(f, "{}]", self.value),
+        }
+    }
+}
+
+impl From<FormatSelector> for String {
+    fn from(selector: FormatSelector) -> Self {
+        selector.to_string()
+    }
+}
+
+impl From<&FormatSelector> for String {
+    fn from(selector: &FormatSelector) -> Self {
+        selector.to_string()
+    }
+}
+
+impl FromStr for FormatSelector {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut scanner = Scanner::new(s);
+        let selector = parse_multiple(&mut scanner)?;
+
+        scanner.skip_whitespace();
+        match scanner.is_empty() {
+            true => Ok(selector),
+            false => Err(error(&scanner, "`,`, `/`, `+` or the end of the selector")),
+        }
+    }
+}
+
+fn error(scanner: &Scanner, expected: &'static str) -> ParseError {
+    error_at(scanner, scanner.pos(), expected)
+}
+
+fn error_at(scanner: &Scanner, position: usize, expected: &'static str) -> ParseError {
+    ParseError::new(
+        scanner.input().to_string(),
+        ParseErrorVariant::InvalidFormatSelector { position, expected },
+    )
+}
+
+/// Parses selectors separated by `separator`, collapsing a single selector to itself
+fn parse_separated(
+    scanner: &mut Scanner,
+    separator: char,
+    parse: fn(&mut Scanner) -> Result<FormatSelector, ParseError>,
+    variant: fn(Vec<FormatSelector>) -> FormatSelector,
+) -> Result<FormatSelector, ParseError> {
+    let mut selectors = vec![parse(scanner)?];
+
+    loop {
+        scanner.skip_whitespace();
+        if scanner.peek() != Some(separator) {
+            break;
+        }
+        scanner.bump();
+        selectors.push(parse(scanner)?);
+    }
+
+    match selectors.len() {
+        1 => Ok(selectors.pop().unwrap()),
+        _ => Ok(variant(selectors)),
+    }
+}
+
+fn parse_multiple(scanner: &mut Scanner) -> Result<FormatSelector, ParseError> {
+    parse_separated(scanner, ',', parse_fallback, FormatSelector::Multiple)
+}
+
+fn parse_fallback(scanner: &mut Scanner) -> Result<FormatSelector, ParseError> {
+    parse_separated(scanner, '/', parse_merge, FormatSelector::Fallback)
+}
+
+fn parse_merge(scanner: &mut Scanner) -> Result<FormatSelector, ParseError> {
+    parse_separated(scanner, '+', parse_single, FormatSelector::Merge)
+}
+
+fn parse_single(scanner: &mut Scanner) -> Result<FormatSelector, ParseError> {
+    scanner.skip_whitespace();
+
+    let selector = if scanner.peek() == Some('(') {
+        scanner.bump();
+        let selector = parse_multiple(scanner)?;
+        scanner.skip_whitespace();
+        if !scanner.eat(")") {
+            return Err(error(scanner, "`)`"));
+        }
+        selector
+    } else {
+        FormatSelector::new(parse_spec(scanner)?)
+    };
+
+    let mut filters = Vec::new();
+    while scanner.peek() == Some('[') {
+        filters.push(parse_filter(scanner)?);
+    }
+
+    Ok(match (selector, filters.is_empty()) {
+        (selector, true) => selector,
+        (FormatSelector::Format { spec, .. }, false) => FormatSelector::Format { spec, filters },
+        (selector, false) => FormatSelector::Group {
+            selector: Box::new(selector),
+            filters,
+        },
+    })
+}
+
+fn parse_spec(scanner: &mut Scanner) -> Result<FormatSpec, ParseError> {
+    let start = scanner.pos();
+    // like yt-dlp, anything up to an operator is one spec, e.g. `dash-video=123000` or `hls-2.1`
+    let name = scanner.take_while(|c| !c.is_whitespace() && !"[](),/+".contains(c));
+    if name.is_empty() {
+        return Err(error(scanner, "a format"));
+    }
+
+    let (keyword, nth) = match name.split_once('.') {
+        Some((keyword, nth)) => (keyword, Some(nth)),
+        None => (name, None),
+    };
+    let ranked = match keyword {
+        "all" if nth.is_none() => return Ok(FormatSpec::All),
+        "mergeall" if nth.is_none() => return Ok(FormatSpec::MergeAll),
+        "b" | "best" => Some((true, Streams::Both)),
+        "b*" | "best*" => Some((true, Streams::Any)),
+        "bv" | "bestvideo" => Some((true, Streams::VideoOnly)),
+        "bv*" | "bestvideo*" => Some((true, Streams::Video)),
+        "ba" | "bestaudio" => Some((true, Streams::AudioOnly)),
+        "ba*" | "bestaudio*" => Some((true, Streams::Audio)),
+        "w" | "worst" => Some((false, Streams::Both)),
+        "w*" | "worst*" => Some((false, Streams::Any)),
+        "wv" | "worstvideo" => Some((false, Streams::VideoOnly)),
+        "wv*" | "worstvideo*" => Some((false, Streams::Video)),
+        "wa" | "worstaudio" => Some((false, Streams::AudioOnly)),
+        "wa*" | "worstaudio*" => Some((false, Streams::Audio)),
+        _ => None,
+    };
+
+    let Some((best, streams)) = ranked else {
+        // yt-dlp reads any other word as a format code, which only fails once the formats of a
+        // video are known, so misspelled keywords such as `bets` are caught here
+        if name.contains('*') || is_misspelled_keyword(keyword) {
+            return Err(error_at(
+                scanner,
+                start,
+                "a format code or a `best`/`worst` keyword",
+            ));
+        }
+        return Ok(FormatSpec::Id(name.to_string()));
+    };
+
+    let nth = match nth.map(str::parse) {
+        Some(Ok(nth)) => Some(nth),
+        Some(Err(_)) => {
+            return Err(error_at(
+                scanner,
+                start + keyword.len() + 1,
+                "a number after `.`",
+            ))
+        }
+        None => None,
+    };
+
+    Ok(match best {
+        true => FormatSpec::Best(streams, nth),
+        false => FormatSpec::Worst(streams, nth),
+    })
+}
+
+/// Whether `word` is one edit away from a long keyword, shorter keywords such as `bv` are too
+/// close to real format codes such as `hd`
+fn is_misspelled_keyword(word: &str) -> bool {
+    const KEYWORDS: [&str; 7] = [
+        "best",
+        "bestvideo",
+        "bestaudio",
+        "worst",
+        "worstvideo",
+        "worstaudio",
+        "mergeall",
+    ];
+
+    word.chars().all(|c| c.is_ascii_lowercase())
+        && KEYWORDS
+            .iter()
+            .any(|keyword| is_one_edit_apart(word.as_bytes(), keyword.as_bytes()))
+}
+
+/// Whether `a` and `b` differ by exactly one substitution, insertion, deletion or swap of
+/// adjacent characters
+fn is_one_edit_apart(a: &[u8], b: &[u8]) -> bool {
+    let common = a.iter().zip(b).take_while(|(a, b)| a == b).count();
+    let (a, b) = (&a[common..], &b[common..]);
+
+    match (a, b) {
+        ([], []) => false,
+        ([], [_]) | ([_], []) => true,
+        ([a0, a1, a @ ..], [b0, b1, b @ ..]) if a0 == b1 && a1 == b0 && a == b => true,
+        _ => a.get(1..) == b.get(1..) || a.get(1..) == Some(b) || Some(a) == b.get(1..),
+    }
+}
+
+fn parse_filter(scanner: &mut Scanner) -> Result<FormatFilter, ParseError> {
+    scanner.bump();
+    scanner.skip_whitespace();
+
+    let key = scanner.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+    if key.is_empty() {
+        return Err(error(scanner, "a field name"));
+    }
+    scanner.skip_whitespace();
+
+    let Some(comparison) = Comparison::ALL
+        .into_iter()
+        .find(|comparison| scanner.eat(comparison.operator()))
+    else {
+        return Err(error(scanner, "a comparison operator"));
+    };
+    let none_inclusive = scanner.eat("?");
+    scanner.skip_whitespace();
+
+    let value = match scanner.peek() {
+        Some(quote @ ('\'' | '"')) => {
+            scanner.bump();
+            // yt-dlp only unescapes the quote character, other backslashes are kept
+            let mut value = String::new();
+            loop {
+                match scanner.bump() {
+                    Some(c) if c == quote => break,
+                    Some('\\') if scanner.peek() == Some(quote) => {
+                        scanner.bump();
+                        value.push(quote);
+                    }
+                    Some(c) => value.push(c),
+                    None => return Err(error(scanner, "a closing quote")),
+                }
+            }
+            scanner.skip_whitespace();
+            value
+        }
+        _ => match scanner.take_while(|c| c != ']').trim_end() {
+            "" => return Err(error(scanner, "a value")),
+            value => value.to_string(),
+        },
+    };
+
+    if !scanner.eat("]") {
+        return Err(error(scanner, "`]`"));
+    }
+
+    Ok(FormatFilter {
+        key: key.to_string(),
+        comparison,
+        value,
+        none_inclusive,
+    })
+}
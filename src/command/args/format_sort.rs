@@ -0,0 +1,313 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{ParseError, ParseErrorVariant};
+
+/// Sort order for `--format-sort`, passed to [`Builder::format_sort`](crate::Builder::format_sort)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormatSort(Vec<SortKey>);
+
+/// One comma separated entry of [`FormatSort`], e.g. `+size` or `res:1080`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: SortField,
+    /// Prefer smaller values instead of larger ones, the `+` prefix
+    pub reverse: bool,
+    pub preference: Option<SortPreference>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SortPreference {
+    /// `field:value`, prefer values up to and including `value`
+    Limit(String),
+    /// `field~value`, prefer values closest to `value`
+    Closest(String),
+}
+
+/// Fields formats can be sorted by, see "Sorting Formats" in the yt-dlp documentation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    /// `hasvid`
+    HasVideo,
+    /// `hasaud`
+    HasAudio,
+    /// `ie_pref`
+    IePreference,
+    /// `lang`
+    Language,
+    /// `quality`
+    Quality,
+    /// `source`
+    Source,
+    /// `proto`
+    Protocol,
+    /// `vcodec`
+    VideoCodec,
+    /// `acodec`
+    AudioCodec,
+    /// `codec`
+    Codec,
+    /// `vext`
+    VideoExt,
+    /// `aext`
+    AudioExt,
+    /// `ext`
+    Ext,
+    /// `filesize`
+    Filesize,
+    /// `fs_approx`
+    FilesizeApprox,
+    /// `size`
+    Size,
+    /// `height`
+    Height,
+    /// `width`
+    Width,
+    /// `res`
+    Resolution,
+    /// `fps`
+    Fps,
+    /// `hdr`
+    Hdr,
+    /// `channels`
+    Channels,
+    /// `tbr`
+    TotalBitrate,
+    /// `vbr`
+    VideoBitrate,
+    /// `abr`
+    AudioBitrate,
+    /// `br`
+    Bitrate,
+    /// `asr`
+    SampleRate,
+}
+
+impl SortField {
+    const ALL: [Self; 27] = [
+        Self::HasVideo,
+        Self::HasAudio,
+        Self::IePreference,
+        Self::Language,
+        Self::Quality,
+        Self::Source,
+        Self::Protocol,
+        Self::VideoCodec,
+        Self::AudioCodec,
+        Self::Codec,
+        Self::VideoExt,
+        Self::AudioExt,
+        Self::Ext,
+        Self::Filesize,
+        Self::FilesizeApprox,
+        Self::Size,
+        Self::Height,
+        Self::Width,
+        Self::Resolution,
+        Self::Fps,
+        Self::Hdr,
+        Self::Channels,
+        Self::TotalBitrate,
+        Self::VideoBitrate,
+        Self::AudioBitrate,
+        Self::Bitrate,
+        Self::SampleRate,
+    ];
+
+    /// Name of the field as used by yt-dlp
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::HasVideo => "hasvid",
+            Self::HasAudio => "hasaud",
+            Self::IePreference => "ie_pref",
+            Self::Language => "lang",
+            Self::Quality => "quality",
+            Self::Source => "source",
+            Self::Protocol => "proto",
+            Self::VideoCodec => "vcodec",
+            Self::AudioCodec => "acodec",
+            Self::Codec => "codec",
+            Self::VideoExt => "vext",
+            Self::AudioExt => "aext",
+            Self::Ext => "ext",
+            Self::Filesize => "filesize",
+            Self::FilesizeApprox => "fs_approx",
+            Self::Size => "size",
+            Self::Height => "height",
+            Self::Width => "width",
+            Self::Resolution => "res",
+            Self::Fps => "fps",
+            Self::Hdr => "hdr",
+            Self::Channels => "channels",
+            Self::TotalBitrate => "tbr",
+            Self::VideoBitrate => "vbr",
+            Self::AudioBitrate => "abr",
+            Self::Bitrate => "br",
+            Self::SampleRate => "asr",
+        }
+    }
+}
+
+impl FormatSort {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a key, used to break ties left by the previous keys
+    pub fn then(mut self, key: impl Into<SortKey>) -> Self {
+        self.0.push(key.into());
+        self
+    }
+
+    pub fn keys(&self) -> &[SortKey] {
+        &self.0
+    }
+}
+
+impl SortKey {
+    pub fn new(field: SortField) -> Self {
+        Self {
+            field,
+            reverse: false,
+            preference: None,
+        }
+    }
+
+    /// Prefer smaller values instead of larger ones
+    pub fn reversed(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Prefer values up to and including `value`, e.g. `res:1080`
+    pub fn limit(mut self, value: impl ToString) -> Self {
+        self.preference = Some(SortPreference::Limit(value.to_string()));
+        self
+    }
+
+    /// Prefer values closest to `value`, e.g. `filesize~1G`
+    pub fn closest(mut self, value: impl ToString) -> Self {
+        self.preference = Some(SortPreference::Closest(value.to_string()));
+        self
+    }
+}
+
+impl From<SortField> for SortKey {
+    fn from(field: SortField) -> Self {
+        Self::new(field)
+    }
+}
+
+impl From<Vec<SortKey>> for FormatSort {
+    fn from(keys: Vec<SortKey>) -> Self {
+        Self(keys)
+    }
+}
+
+impl From<FormatSort> for String {
+    fn from(sort: FormatSort) -> Self {
+        sort.to_string()
+    }
+}
+
+impl From<&FormatSort> for String {
+    fn from(sort: &FormatSort) -> Self {
+        sort.to_string()
+    }
+}
+
+impl Display for FormatSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, key) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{key}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for SortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.reverse {
+            f.write_str("+")?;
+        }
+        f.write_str(self.field.name())?;
+
+        match &self.preference {
+            Some(SortPreference::Limit(value)) => write!(f, ":{value}"),
+            Some(SortPreference::Closest(value)) => write!(f, "~{value}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Display for SortField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for FormatSort {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|key| SortKey::from_str(key.trim()).map_err(|e| e.replace(s.to_string())))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+impl FromStr for SortKey {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (reverse, rest) = match s.strip_prefix('+') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+
+        let (field, preference) = match rest.find([':', '~']) {
+            Some(i) => {
+                let value = rest[i + 1..].to_string();
+                if value.is_empty() {
+                    return Err(ParseError::new(
+                        s.to_string(),
+                        ParseErrorVariant::MissingSortValue(rest[..i].to_string()),
+                    ));
+                }
+
+                let preference = match &rest[i..=i] {
+                    ":" => SortPreference::Limit(value),
+                    _ => SortPreference::Closest(value),
+                };
+                (&rest[..i], Some(preference))
+            }
+            None => (rest, None),
+        };
+
+        Ok(Self {
+            field: SortField::from_str(field).map_err(|e| e.replace(s.to_string()))?,
+            reverse,
+            preference,
+        })
+    }
+}
+
+impl FromStr for SortField {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.name() == s)
+            .ok_or_else(|| {
+                ParseError::new(
+                    s.to_string(),
+                    ParseErrorVariant::UnknownSortField(s.to_string()),
+                )
+            })
+    }
+}
@@ -0,0 +1,396 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::{ParseError, ParseErrorVariant, VideoInfo};
+
+use super::Comparison;
+
+/// A filter for `--match-filters` and `--break-match-filters`, matching if all of its
+/// conditions hold
+///
+/// Passing several filters to yt-dlp matches a video if any one of them does.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MatchFilter(Vec<MatchCondition>);
+
+/// One `&` separated part of a [`MatchFilter`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchCondition {
+    /// `field`, the field is available, or true for booleans
+    Present(String),
+    /// `!field`, the field is unavailable, or false for booleans
+    Absent(String),
+    /// `field<value`, `field~='regex'` etc.
+    Compare {
+        key: String,
+        comparison: Comparison,
+        value: String,
+        /// Also match if the field is unavailable, the `?` in `like_count>?100`
+        none_inclusive: bool,
+    },
+}
+
+impl MatchFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a condition that must also hold
+    pub fn and(mut self, condition: MatchCondition) -> Self {
+        self.0.push(condition);
+        self
+    }
+
+    pub fn conditions(&self) -> &[MatchCondition] {
+        &self.0
+    }
+
+    /// Whether yt-dlp would accept `info` with this filter
+    ///
+    /// Regular expressions are evaluated with the `regex` crate, which does not support
+    /// look-around and backreferences like Python does.
+    pub fn evaluate(&self, info: &VideoInfo) -> bool {
+        let info = serde_json::to_value(info).unwrap_or_default();
+        self.0.iter().all(|condition| condition.evaluate(&info))
+    }
+}
+
+impl MatchCondition {
+    pub fn present(key: &str) -> Self {
+        Self::Present(key.to_string())
+    }
+
+    pub fn absent(key: &str) -> Self {
+        Self::Absent(key.to_string())
+    }
+
+    pub fn compare(key: &str, comparison: Comparison, value: impl ToString) -> Self {
+        Self::Compare {
+            key: key.to_string(),
+            comparison,
+            value: value.to_string(),
+            none_inclusive: false,
+        }
+    }
+
+    /// Also match if the compared field is unavailable, no effect on other conditions
+    pub fn none_inclusive(mut self) -> Self {
+        if let Self::Compare { none_inclusive, .. } = &mut self {
+            *none_inclusive = true;
+        }
+        self
+    }
+
+    fn evaluate(&self, info: &Value) -> bool {
+        let field = |key: &str| info.get(key).filter(|value| !value.is_null());
+
+        match self {
+            Self::Present(key) => match field(key) {
+                Some(Value::Bool(value)) => *value,
+                value => value.is_some(),
+            },
+            Self::Absent(key) => match field(key) {
+                Some(Value::Bool(value)) => !*value,
+                value => value.is_none(),
+            },
+            Self::Compare {
+                key,
+                comparison,
+                value,
+                none_inclusive,
+            } => match field(key) {
+                Some(actual) => compare(actual, *comparison, value),
+                None => *none_inclusive,
+            },
+        }
+    }
+}
+
+fn compare(actual: &Value, comparison: Comparison, value: &str) -> bool {
+    let number = match actual {
+        Value::Number(number) => number.as_f64(),
+        Value::Bool(value) => Some(f64::from(u8::from(*value))),
+        _ => None,
+    };
+
+    if let Some(actual) = number {
+        let Some(value) = parse_number(value) else {
+            return false;
+        };
+        let ordering = actual.partial_cmp(&value);
+
+        return match comparison {
+            Comparison::Lt => ordering == Some(Ordering::Less),
+            Comparison::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+            Comparison::Gt => ordering == Some(Ordering::Greater),
+            Comparison::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+            Comparison::Eq => ordering == Some(Ordering::Equal),
+            Comparison::Ne => ordering != Some(Ordering::Equal),
+            // yt-dlp refuses string operators on numbers
+            _ => false,
+        };
+    }
+
+    if let Value::Array(values) = actual {
+        let contains = values.iter().any(|item| item.as_str() == Some(value));
+        return match comparison {
+            Comparison::Contains => contains,
+            Comparison::NotContains => !contains,
+            Comparison::Ne => true,
+            _ => false,
+        };
+    }
+
+    let Some(actual) = actual.as_str() else {
+        return false;
+    };
+    let matches = || Regex::new(value).is_ok_and(|regex| regex.is_match(actual));
+
+    match comparison {
+        Comparison::Lt => actual < value,
+        Comparison::Le => actual <= value,
+        Comparison::Gt => actual > value,
+        Comparison::Ge => actual >= value,
+        Comparison::Eq => actual == value,
+        Comparison::Ne => actual != value,
+        Comparison::StartsWith => actual.starts_with(value),
+        Comparison::NotStartsWith => !actual.starts_with(value),
+        Comparison::EndsWith => actual.ends_with(value),
+        Comparison::NotEndsWith => !actual.ends_with(value),
+        Comparison::Contains => actual.contains(value),
+        Comparison::NotContains => !actual.contains(value),
+        Comparison::Matches => matches(),
+        Comparison::NotMatches => !matches(),
+    }
+}
+
+/// Parses a comparison value the way yt-dlp does for numeric fields: as a plain number, a file
+/// size such as `10M` or `1.5GiB`, or a duration such as `1:30:00` or `90s`
+fn parse_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if let Ok(number) = value.parse() {
+        return Some(number);
+    }
+
+    parse_filesize(value)
+        .or_else(|| parse_filesize(&format!("{value}B")))
+        .or_else(|| parse_duration(value))
+}
+
+fn parse_filesize(value: &str) -> Option<f64> {
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number = number.parse::<f64>().ok()?;
+
+    let multiplier = match unit.trim() {
+        "B" | "b" | "bytes" => 1.,
+        unit => {
+            let mut chars = unit.chars();
+            let prefix = chars.next()?;
+            let power = "KMGTPEZY".find(prefix.to_ascii_uppercase())? as i32 + 1;
+            match (prefix.is_ascii_uppercase(), chars.as_str()) {
+                (_, "iB") => 1024f64.powi(power),
+                (true, "B") | (_, "b") => 1000f64.powi(power),
+                (false, "B") => 1024f64.powi(power),
+                _ => return None,
+            }
+        }
+    };
+
+    Some((number * multiplier).trunc())
+}
+
+fn parse_duration(value: &str) -> Option<f64> {
+    if value.contains(':') {
+        return value.split(':').try_fold(0., |total, part| {
+            part.parse::<f64>().ok().map(|part| total * 60. + part)
+        });
+    }
+
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number = number.parse::<f64>().ok()?;
+
+    Some(
+        number
+            * match unit.trim() {
+                "s" | "sec" | "secs" | "seconds" => 1.,
+                "m" | "min" | "mins" | "minutes" => 60.,
+                "h" | "hr" | "hrs" | "hours" => 3600.,
+                "d" | "day" | "days" => 86400.,
+                _ => return None,
+            },
+    )
+}
+
+impl Display for MatchFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, condition) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(" & ")?;
+            }
+            write!(f, "{condition}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for MatchCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (key, comparison, value, none_inclusive) = match self {
+            Self::Present(key) => return f.write_str(key),
+            Self::Absent(key) => return write!(f, "!{key}"),
+            Self::Compare {
+                key,
+                comparison,
+                value,
+                none_inclusive,
+            } => (key, comparison, value, none_inclusive),
+        };
+
+        let value =
+            match value.starts_with(['\'', '"']) || value.trim() != value || value.is_empty() {
+                true => format!("'{}'", value.replace('\'', r"\'")),
+                false => value.clone(),
+            };
+
+        write!(
+            f,
+            "{key}{}{}{}",
+            comparison.operator(),
+            if *none_inclusive { "?" } else { "" },
+            // `&` separates conditions before quotes are considered, so it is escaped everywhere
+            value.replace('&', r"\&")
+        )
+    }
+}
+
+impl From<MatchFilter> for String {
+    fn from(filter: MatchFilter) -> Self {
+        filter.to_string()
+    }
+}
+
+impl From<&MatchFilter> for String {
+    fn from(filter: &MatchFilter) -> Self {
+        filter.to_string()
+    }
+}
+
+impl FromStr for MatchFilter {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut conditions = Vec::new();
+        let mut part = String::new();
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' if chars.peek() == Some(&'&') => part.push(chars.next().unwrap()),
+                '&' => conditions.push(std::mem::take(&mut part)),
+                c => part.push(c),
+            }
+        }
+        conditions.push(part);
+
+        let conditions = conditions
+            .iter()
+            .map(|part| MatchCondition::from_str(part).map_err(|e| e.replace(s.to_string())))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self(conditions))
+    }
+}
+
+impl FromStr for MatchCondition {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || {
+            ParseError::new(
+                s.to_string(),
+                ParseErrorVariant::InvalidMatchCondition(s.trim().to_string()),
+            )
+        };
+        let is_key = |key: &str| {
+            !key.is_empty()
+                && key
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        };
+
+        let trimmed = s.trim();
+        if let Some(key) = trimmed.strip_prefix('!') {
+            return match is_key(key.trim_start()) {
+                true => Ok(Self::absent(key.trim_start())),
+                false => Err(error()),
+            };
+        }
+
+        let key_end = trimmed
+            .find(|c: char| !(c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'))
+            .unwrap_or(trimmed.len());
+        let (key, rest) = trimmed.split_at(key_end);
+        if !is_key(key) {
+            return Err(error());
+        }
+
+        let rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok(Self::present(key));
+        }
+
+        let (negated, rest) = match rest.strip_prefix('!') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, rest),
+        };
+        let (operator, rest) = ["*=", "^=", "$=", "~=", "<=", "<", ">=", ">", "="]
+            .into_iter()
+            .find_map(|operator| rest.strip_prefix(operator).map(|rest| (operator, rest)))
+            .ok_or_else(error)?;
+        let rest = rest.trim_start();
+        let (none_inclusive, rest) = match rest.strip_prefix('?') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, rest),
+        };
+
+        let value = match rest.chars().next() {
+            Some(quote @ ('\'' | '"')) if rest.len() >= 2 && rest.ends_with(quote) => {
+                rest[1..rest.len() - 1].replace(&format!("\\{quote}"), &quote.to_string())
+            }
+            Some(_) => rest.to_string(),
+            None => return Err(error()),
+        };
+
+        let comparison = match (operator, negated) {
+            ("<", false) | (">=", true) => Comparison::Lt,
+            ("<=", false) | (">", true) => Comparison::Le,
+            (">", false) | ("<=", true) => Comparison::Gt,
+            (">=", false) | ("<", true) => Comparison::Ge,
+            ("=", false) => Comparison::Eq,
+            ("=", true) => Comparison::Ne,
+            ("^=", false) => Comparison::StartsWith,
+            ("^=", true) => Comparison::NotStartsWith,
+            ("$=", false) => Comparison::EndsWith,
+            ("$=", true) => Comparison::NotEndsWith,
+            ("*=", false) => Comparison::Contains,
+            ("*=", true) => Comparison::NotContains,
+            ("~=", false) => Comparison::Matches,
+            (_, _) => Comparison::NotMatches,
+        };
+
+        Ok(Self::Compare {
+            key: key.to_string(),
+            comparison,
+            value,
+            none_inclusive,
+        })
+    }
+}
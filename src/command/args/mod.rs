@@ -0,0 +1,28 @@
+mod audio_extraction;
+mod byte_size;
+mod download_section;
+mod format_selector;
+mod format_sort;
+mod match_filter;
+mod output_layout;
+mod output_template;
+mod playlist_items;
+mod post_processor;
+mod retries;
+mod scanner;
+mod sponsorblock;
+mod yt_date;
+
+pub use audio_extraction::*;
+pub use byte_size::*;
+pub use download_section::*;
+pub use format_selector::*;
+pub use format_sort::*;
+pub use match_filter::*;
+pub use output_layout::*;
+pub use output_template::*;
+pub use playlist_items::*;
+pub use post_processor::*;
+pub use retries::*;
+pub use sponsorblock::*;
+pub use yt_date::*;
@@ -0,0 +1,143 @@
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::Builder;
+
+use super::OutputTemplate;
+
+/// Output templates and paths for every kind of file yt-dlp writes, applied with
+/// [`Builder::output_layout`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutputLayout {
+    template: Option<OutputTemplate>,
+    templates: BTreeMap<OutputType, OutputTemplate>,
+    home: Option<PathBuf>,
+    temp: Option<PathBuf>,
+    paths: BTreeMap<OutputType, PathBuf>,
+}
+
+/// Kinds of files that can be given their own template with `--output TYPE:TEMPLATE` or their own
+/// directory with `--paths TYPE:PATH`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OutputType {
+    Subtitle,
+    Thumbnail,
+    Description,
+    InfoJson,
+    Link,
+    PlaylistThumbnail,
+    PlaylistDescription,
+    PlaylistInfoJson,
+    Chapter,
+    PlaylistVideo,
+}
+
+impl OutputType {
+    /// Name of the type as used by yt-dlp
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Subtitle => "subtitle",
+            Self::Thumbnail => "thumbnail",
+            Self::Description => "description",
+            Self::InfoJson => "infojson",
+            Self::Link => "link",
+            Self::PlaylistThumbnail => "pl_thumbnail",
+            Self::PlaylistDescription => "pl_description",
+            Self::PlaylistInfoJson => "pl_infojson",
+            Self::Chapter => "chapter",
+            Self::PlaylistVideo => "pl_video",
+        }
+    }
+}
+
+impl Display for OutputType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl OutputLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Template for the downloaded media and any type without its own template
+    pub fn template(mut self, template: OutputTemplate) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    /// Template for one type of file, replacing any previous template for it
+    pub fn template_for(mut self, r#type: OutputType, template: OutputTemplate) -> Self {
+        self.templates.insert(r#type, template);
+        self
+    }
+
+    /// Directory the finished files are moved to
+    pub fn home(mut self, path: impl Into<PathBuf>) -> Self {
+        self.home = Some(path.into());
+        self
+    }
+
+    /// Directory intermediary files are downloaded to
+    pub fn temp(mut self, path: impl Into<PathBuf>) -> Self {
+        self.temp = Some(path.into());
+        self
+    }
+
+    /// Directory for one type of file, replacing any previous path for it
+    pub fn path_for(mut self, r#type: OutputType, path: impl Into<PathBuf>) -> Self {
+        self.paths.insert(r#type, path.into());
+        self
+    }
+
+    /// Every `--output` value, the default template first
+    pub fn output_args(&self) -> Vec<String> {
+        self.template
+            .iter()
+            .map(OutputTemplate::to_string)
+            .chain(
+                self.templates
+                    .iter()
+                    .map(|(r#type, template)| format!("{type}:{template}")),
+            )
+            .collect()
+    }
+
+    /// Every `--paths` value, `home` and `temp` first
+    pub fn paths_args(&self) -> Vec<OsString> {
+        let prefixed = |prefix: &str, path: &Path| {
+            let mut arg = OsString::from(format!("{prefix}:"));
+            arg.push(path);
+            arg
+        };
+
+        self.home
+            .iter()
+            .map(|path| prefixed("home", path))
+            .chain(self.temp.iter().map(|path| prefixed("temp", path)))
+            .chain(
+                self.paths
+                    .iter()
+                    .map(|(r#type, path)| prefixed(r#type.name(), path)),
+            )
+            .collect()
+    }
+}
+
+impl Builder {
+    /// Pass every template and path of `layout` with `--output` and `--paths`
+    pub fn output_layout(&mut self, layout: &OutputLayout) -> &mut Self {
+        for template in layout.output_args() {
+            self.output(template);
+        }
+        for path in layout.paths_args() {
+            self.command_mut().arg("--paths").arg(path);
+        }
+        self
+    }
+}
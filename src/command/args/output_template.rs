@@ -0,0 +1,618 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde_json::Value;
+
+use crate::{ParseError, ParseErrorVariant, VideoInfo};
+
+use super::scanner::Scanner;
+
+/// An output filename template, passed to [`Builder::output`](crate::Builder::output)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutputTemplate(Vec<TemplatePart>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplatePart {
+    /// Text copied into the filename as is
+    Literal(String),
+    /// A `%(...)s` field
+    Field(TemplateField),
+}
+
+/// A field such as `%(title).50s` or `%(release_date>%Y-%m-%d,upload_date|Unknown)s`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateField {
+    /// The field and its alternates separated by `,`, the first one that is available is used
+    pub alternatives: Vec<FieldExpression>,
+    /// `&replacement`, used instead of the value when it is available, `{}` is replaced with it
+    pub replacement: Option<String>,
+    /// `|default`, used when none of the alternatives are available
+    pub default: Option<String>,
+    /// printf-style flags, e.g. `0` in `%(autonumber)03d`
+    pub flags: String,
+    pub width: Option<usize>,
+    pub precision: Option<usize>,
+    /// Conversion type, e.g. `s`, `d`, `f`, `j` or `l`
+    pub conversion: char,
+}
+
+/// One alternative of a [`TemplateField`], e.g. `playlist_index-1` or `upload_date>%Y`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldExpression {
+    /// Field name, optionally traversing objects and lists with `.`, e.g. `formats.-1.format_id`
+    pub path: String,
+    /// Arithmetic applied in order, with a number or another field as operand
+    pub maths: Vec<(MathOp, String)>,
+    /// `>format`, strftime format applied to dates and timestamps
+    pub date_format: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathOp {
+    Add,
+    Sub,
+    Mul,
+}
+
+/// Value used by yt-dlp for unavailable fields without a default (`--output-na-placeholder`)
+const NA_PLACEHOLDER: &str = "NA";
+
+/// Conversion types accepted after a field
+const CONVERSIONS: &str = "diouxXeEfFgGcrsajlqhBUDS";
+
+impl OutputTemplate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn literal(mut self, text: &str) -> Self {
+        match self.0.last_mut() {
+            Some(TemplatePart::Literal(literal)) => literal.push_str(text),
+            _ => self.0.push(TemplatePart::Literal(text.to_string())),
+        }
+        self
+    }
+
+    pub fn field(mut self, field: TemplateField) -> Self {
+        self.0.push(TemplatePart::Field(field));
+        self
+    }
+
+    pub fn parts(&self) -> &[TemplatePart] {
+        &self.0
+    }
+
+    /// Predict the filename yt-dlp produces for `info`
+    ///
+    /// This follows yt-dlp's rules for the common conversions and replaces characters that are
+    /// not allowed in filenames the way yt-dlp does without `--restrict-filenames`.
+    pub fn render(&self, info: &VideoInfo) -> String {
+        let info = serde_json::to_value(info).unwrap_or_default();
+
+        self.0
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Literal(literal) => literal.clone(),
+                TemplatePart::Field(field) => sanitize(&field.render(&info)),
+            })
+            .collect()
+    }
+}
+
+impl TemplateField {
+    pub fn new(path: &str) -> Self {
+        Self {
+            alternatives: vec![FieldExpression::new(path)],
+            replacement: None,
+            default: None,
+            flags: String::new(),
+            width: None,
+            precision: None,
+            conversion: 's',
+        }
+    }
+
+    /// Add an alternate field, used if the previous ones are unavailable
+    pub fn or(mut self, path: &str) -> Self {
+        self.alternatives.push(FieldExpression::new(path));
+        self
+    }
+
+    /// Format the last added field as a date, e.g. `%Y-%m-%d`
+    pub fn date(mut self, format: &str) -> Self {
+        if let Some(expression) = self.alternatives.last_mut() {
+            expression.date_format = Some(format.to_string());
+        }
+        self
+    }
+
+    /// Apply arithmetic to the last added field, `operand` is a number or a field name
+    pub fn math(mut self, op: MathOp, operand: impl ToString) -> Self {
+        if let Some(expression) = self.alternatives.last_mut() {
+            expression.maths.push((op, operand.to_string()));
+        }
+        self
+    }
+
+    pub fn replacement(mut self, replacement: &str) -> Self {
+        self.replacement = Some(replacement.to_string());
+        self
+    }
+
+    pub fn default(mut self, default: &str) -> Self {
+        self.default = Some(default.to_string());
+        self
+    }
+
+    pub fn flags(mut self, flags: &str) -> Self {
+        self.flags = flags.to_string();
+        self
+    }
+
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    pub fn conversion(mut self, conversion: char) -> Self {
+        self.conversion = conversion;
+        self
+    }
+
+    fn render(&self, info: &Value) -> String {
+        let value = self
+            .alternatives
+            .iter()
+            .find_map(|expression| expression.evaluate(info));
+
+        match (value, &self.replacement) {
+            (Some(value), Some(replacement)) => {
+                self.pad(replacement.replace("{}", &python_str(&value)), false)
+            }
+            (Some(value), None) => self.convert(&value),
+            (None, _) => self.pad(
+                self.default.clone().unwrap_or(NA_PLACEHOLDER.to_string()),
+                false,
+            ),
+        }
+    }
+
+    fn convert(&self, value: &Value) -> String {
+        let number = value
+            .as_f64()
+            .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()));
+
+        match (self.conversion, number) {
+            ('d' | 'i' | 'u', Some(number)) => self.pad((number.trunc() as i64).to_string(), true),
+            ('x', Some(number)) => self.pad(format!("{:x}", number.trunc() as i64), true),
+            ('X', Some(number)) => self.pad(format!("{:X}", number.trunc() as i64), true),
+            ('o', Some(number)) => self.pad(format!("{:o}", number.trunc() as i64), true),
+            ('e' | 'E' | 'f' | 'F' | 'g' | 'G', Some(number)) => {
+                let precision = self.precision.unwrap_or(6);
+                self.pad(format!("{number:.precision$}"), true)
+            }
+            ('j', _) => match self.flags.contains('#') {
+                true => serde_json::to_string_pretty(value),
+                false => serde_json::to_string(value),
+            }
+            .unwrap_or_default(),
+            ('l', _) => {
+                let separator = if self.flags.contains('#') { "\n" } else { ", " };
+                let text = match value {
+                    Value::Array(values) => values
+                        .iter()
+                        .map(python_str)
+                        .collect::<Vec<_>>()
+                        .join(separator),
+                    value => python_str(value),
+                };
+                self.truncate_and_pad(text)
+            }
+            ('q', _) => {
+                let text = python_str(value);
+                self.pad(format!("'{}'", text.replace('\'', r"'\''")), false)
+            }
+            _ => self.truncate_and_pad(python_str(value)),
+        }
+    }
+
+    fn truncate_and_pad(&self, text: String) -> String {
+        match self.precision {
+            Some(precision) => self.pad(text.chars().take(precision).collect(), false),
+            None => self.pad(text, false),
+        }
+    }
+
+    fn pad(&self, text: String, numeric: bool) -> String {
+        let width = self.width.unwrap_or(0);
+        let len = text.chars().count();
+        if len >= width {
+            return text;
+        }
+
+        let padding = width - len;
+        if self.flags.contains('-') {
+            format!("{text}{}", " ".repeat(padding))
+        } else if numeric && self.flags.contains('0') {
+            match text.strip_prefix('-') {
+                Some(digits) => format!("-{}{digits}", "0".repeat(padding)),
+                None => format!("{}{text}", "0".repeat(padding)),
+            }
+        } else {
+            format!("{}{text}", " ".repeat(padding))
+        }
+    }
+}
+
+impl FieldExpression {
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            maths: Vec::new(),
+            date_format: None,
+        }
+    }
+
+    fn evaluate(&self, info: &Value) -> Option<Value> {
+        let mut value = lookup(info, &self.path)?;
+
+        for (op, operand) in &self.maths {
+            let operand = match operand.parse::<f64>() {
+                Ok(number) => number,
+                Err(_) => lookup(info, operand)?.as_f64()?,
+            };
+            let lhs = value.as_f64()?;
+
+            let result = match op {
+                MathOp::Add => lhs + operand,
+                MathOp::Sub => lhs - operand,
+                MathOp::Mul => lhs * operand,
+            };
+            value = match result.fract() == 0. && result.abs() < i64::MAX as f64 {
+                true => Value::from(result as i64),
+                false => Value::from(result),
+            };
+        }
+
+        match &self.date_format {
+            Some(format) => strftime(&value, format).map(Value::String),
+            None => Some(value),
+        }
+    }
+}
+
+/// Follows a `.` separated path through objects and lists, negative indices count from the end
+fn lookup(info: &Value, path: &str) -> Option<Value> {
+    let mut value = info;
+
+    for key in path.split('.').filter(|key| !key.is_empty()) {
+        value = match value {
+            Value::Object(map) => map.get(key)?,
+            Value::Array(values) => {
+                let index = key.parse::<i64>().ok()?;
+                let index = match index < 0 {
+                    true => values.len().checked_sub(index.unsigned_abs() as usize)?,
+                    false => index as usize,
+                };
+                values.get(index)?
+            }
+            _ => return None,
+        };
+    }
+
+    match value {
+        Value::Null => None,
+        value => Some(value.clone()),
+    }
+}
+
+/// Renders a value like Python's `str()`, which is what yt-dlp uses for `%(...)s`
+fn python_str(value: &Value) -> String {
+    match value {
+        Value::Null => "None".to_string(),
+        Value::Bool(true) => "True".to_string(),
+        Value::Bool(false) => "False".to_string(),
+        Value::Number(number) if number.is_f64() => {
+            let number = number.as_f64().unwrap_or_default();
+            match number.fract() == 0. && number.abs() < 1e16 {
+                true => format!("{number:.1}"),
+                false => number.to_string(),
+            }
+        }
+        Value::Number(number) => number.to_string(),
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Formats a `YYYYMMDD` date or a unix timestamp, supporting the common strftime directives
+fn strftime(value: &Value, format: &str) -> Option<String> {
+    let (year, month, day, seconds) = match value {
+        Value::String(date) if date.len() == 8 && date.bytes().all(|b| b.is_ascii_digit()) => (
+            date[..4].parse().ok()?,
+            date[4..6].parse().ok()?,
+            date[6..].parse().ok()?,
+            0,
+        ),
+        Value::Number(timestamp) => {
+            let timestamp = timestamp.as_f64()?.floor() as i64;
+            let (year, month, day) = civil_from_days(timestamp.div_euclid(86400));
+            (year, month, day, timestamp.rem_euclid(86400))
+        }
+        _ => return None,
+    };
+
+    let mut out = String::new();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('Y') => out.push_str(&format!("{year:04}")),
+            Some('y') => out.push_str(&format!("{:02}", year.rem_euclid(100))),
+            Some('m') => out.push_str(&format!("{month:02}")),
+            Some('d') => out.push_str(&format!("{day:02}")),
+            Some('H') => out.push_str(&format!("{:02}", seconds / 3600)),
+            Some('M') => out.push_str(&format!("{:02}", seconds / 60 % 60)),
+            Some('S') => out.push_str(&format!("{:02}", seconds % 60)),
+            Some('%') => out.push('%'),
+            Some(other) => {
+                out.push('%');
+                out.push(other);
+            }
+            None => out.push('%'),
+        }
+    }
+
+    Some(out)
+}
+
+/// Converts days since the unix epoch to a proleptic Gregorian `(year, month, day)`
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Replaces characters the way yt-dlp does for filenames without `--restrict-filenames`
+fn sanitize(s: &str) -> String {
+    s.chars()
+        .filter_map(|c| match c {
+            '/' => Some('\u{29F8}'),
+            '\\' => Some('\u{29F9}'),
+            '"' | '*' | ':' | '<' | '>' | '?' | '|' => char::from_u32(c as u32 + 0xFEE0),
+            '\n' => Some(' '),
+            c if (c as u32) < 32 || c as u32 == 127 => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+impl Display for OutputTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|part| match part {
+            TemplatePart::Literal(literal) => f.write_str(&literal.replace('%', "%%")),
+            TemplatePart::Field(field) => write!(f, "{field}"),
+        })
+    }
+}
+
+impl Display for TemplateField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("%(")?;
+        for (i, expression) in self.alternatives.iter().enumerate() {
+            if i != 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{expression}")?;
+        }
+        if let Some(replacement) = &self.replacement {
+            write!(f, "&{replacement}")?;
+        }
+        if let Some(default) = &self.default {
+            write!(f, "|{default}")?;
+        }
+        write!(f, "){}", self.flags)?;
+
+        if let Some(width) = self.width {
+            write!(f, "{width}")?;
+        }
+        if let Some(precision) = self.precision {
+            write!(f, ".{precision}")?;
+        }
+        write!(f, "{}", self.conversion)
+    }
+}
+
+impl Display for FieldExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.path)?;
+        for (op, operand) in &self.maths {
+            write!(f, "{op}{operand}")?;
+        }
+        match &self.date_format {
+            Some(format) => write!(f, ">{format}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Display for MathOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+        })
+    }
+}
+
+impl From<OutputTemplate> for String {
+    fn from(template: OutputTemplate) -> Self {
+        template.to_string()
+    }
+}
+
+impl From<&OutputTemplate> for String {
+    fn from(template: &OutputTemplate) -> Self {
+        template.to_string()
+    }
+}
+
+impl FromStr for OutputTemplate {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut scanner = Scanner::new(s);
+        let mut template = Self::new();
+
+        while let Some(c) = scanner.bump() {
+            if c != '%' {
+                template = template.literal(&c.to_string());
+            } else if scanner.eat("%") {
+                template = template.literal("%");
+            } else if scanner.eat("(") {
+                template = template.field(parse_field(&mut scanner)?);
+            } else {
+                // yt-dlp escapes a lone `%` itself
+                template = template.literal("%");
+            }
+        }
+
+        Ok(template)
+    }
+}
+
+fn error(scanner: &Scanner, expected: &'static str) -> ParseError {
+    ParseError::new(
+        scanner.input().to_string(),
+        ParseErrorVariant::InvalidOutputTemplate {
+            position: scanner.pos(),
+            expected,
+        },
+    )
+}
+
+fn is_path_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn parse_field(scanner: &mut Scanner) -> Result<TemplateField, ParseError> {
+    let mut alternatives = vec![parse_expression(scanner)?];
+    while scanner.eat(",") {
+        alternatives.push(parse_expression(scanner)?);
+    }
+
+    let replacement = match scanner.eat("&") {
+        true => Some(scanner.take_while(|c| c != '|' && c != ')').to_string()),
+        false => None,
+    };
+    let default = match scanner.eat("|") {
+        true => Some(scanner.take_while(|c| c != ')').to_string()),
+        false => None,
+    };
+    if !scanner.eat(")") {
+        return Err(error(scanner, "`,`, `&`, `|` or `)`"));
+    }
+
+    let flags = scanner.take_while(|c| matches!(c, '#' | '0' | '-' | '+' | ' '));
+    let width = scanner.take_while(|c| c.is_ascii_digit()).parse().ok();
+    let precision = match scanner.eat(".") {
+        true => match scanner.take_while(|c| c.is_ascii_digit()).parse() {
+            Ok(precision) => Some(precision),
+            Err(_) => return Err(error(scanner, "a precision after `.`")),
+        },
+        false => None,
+    };
+    // a length modifier is only one if a conversion type follows, `l` alone is a conversion
+    let mut lookahead = scanner.rest().chars();
+    if matches!(lookahead.next(), Some('h' | 'l' | 'L'))
+        && lookahead.next().is_some_and(|c| CONVERSIONS.contains(c))
+    {
+        scanner.bump();
+    }
+
+    let conversion = match scanner.peek() {
+        Some(c) if CONVERSIONS.contains(c) => {
+            scanner.bump();
+            c
+        }
+        _ => return Err(error(scanner, "a conversion type such as `s` or `d`")),
+    };
+
+    Ok(TemplateField {
+        alternatives,
+        replacement,
+        default,
+        flags: flags.to_string(),
+        width,
+        precision,
+        conversion,
+    })
+}
+
+fn parse_path<'a>(scanner: &mut Scanner<'a>) -> &'a str {
+    let start = scanner.pos();
+    scanner.take_while(is_path_char);
+
+    while scanner.rest().starts_with('.') {
+        scanner.bump();
+        scanner.eat("-");
+        scanner.take_while(|c| is_path_char(c) || c == ':');
+    }
+
+    &scanner.input()[start..scanner.pos()]
+}
+
+fn parse_expression(scanner: &mut Scanner) -> Result<FieldExpression, ParseError> {
+    let mut expression = FieldExpression::new(parse_path(scanner));
+
+    loop {
+        let op = match scanner.peek() {
+            Some('+') => MathOp::Add,
+            Some('-') => MathOp::Sub,
+            Some('*') => MathOp::Mul,
+            _ => break,
+        };
+        scanner.bump();
+
+        let operand = match scanner.peek() {
+            Some(c) if c.is_ascii_digit() || c == '-' => {
+                let sign = if scanner.eat("-") { "-" } else { "" };
+                let number = scanner.take_while(|c| c.is_ascii_digit() || c == '.');
+                format!("{sign}{number}")
+            }
+            _ => parse_path(scanner).to_string(),
+        };
+        if operand.is_empty() || operand == "-" {
+            return Err(error(scanner, "a number or field after the operator"));
+        }
+        expression.maths.push((op, operand));
+    }
+
+    if scanner.eat(">") {
+        let format = scanner.take_while(|c| !matches!(c, ',' | '&' | '|' | ')'));
+        if format.is_empty() {
+            return Err(error(scanner, "a date format after `>`"));
+        }
+        expression.date_format = Some(format.to_string());
+    }
+
+    Ok(expression)
+}
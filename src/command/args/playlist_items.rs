@@ -0,0 +1,269 @@
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    ops::{RangeFrom, RangeFull, RangeInclusive, RangeToInclusive},
+    str::FromStr,
+};
+
+use crate::{ParseError, ParseErrorVariant};
+
+/// Items to download from a playlist, for [`Builder::playlist_items`](crate::Builder::playlist_items),
+/// e.g. `1:3,7,-5::2`
+///
+/// Indices start at 1, negative indices count from the end of the playlist.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlaylistItems(Vec<PlaylistItem>);
+
+/// One comma separated entry of [`PlaylistItems`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaylistItem {
+    /// `7` or `-5`
+    Index(i64),
+    /// `[START]:[END][:STEP]`, including both `start` and `end`, which default to the first
+    /// and last item (or the other way around for a negative step)
+    Range {
+        start: Option<i64>,
+        end: Option<i64>,
+        step: i64,
+    },
+}
+
+impl PlaylistItems {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an index or a range, e.g. `.then(7)` or `.then(1..=3)`
+    pub fn then(mut self, item: impl Into<PlaylistItem>) -> Self {
+        self.0.push(item.into());
+        self
+    }
+
+    pub fn items(&self) -> &[PlaylistItem] {
+        &self.0
+    }
+
+    /// Indices (starting at 1) of the items yt-dlp downloads from a playlist of `len` items,
+    /// in the order they are downloaded
+    ///
+    /// Items selected by multiple entries are downloaded once, at their first position, like
+    /// yt-dlp does, e.g. `1:3,2` selects 1, 2 and 3.
+    pub fn indices(&self, len: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut seen = HashSet::new();
+        self.0
+            .iter()
+            .flat_map(move |item| item.indices(len))
+            .filter(move |index| seen.insert(*index))
+    }
+}
+
+impl PlaylistItem {
+    /// A range from `start` to `end`, open ended if `None`
+    pub fn range(start: Option<i64>, end: Option<i64>) -> Self {
+        Self::Range {
+            start,
+            end,
+            step: 1,
+        }
+    }
+
+    /// Select every `step`th item of a range, going backwards if negative, no effect on indices
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is 0.
+    pub fn step(mut self, step: i64) -> Self {
+        assert!(step != 0, "playlist item step cannot be zero");
+        if let Self::Range { step: s, .. } = &mut self {
+            *s = step;
+        }
+        self
+    }
+
+    /// Indices (starting at 1) selected from a playlist of `len` items
+    pub fn indices(&self, len: usize) -> impl Iterator<Item = usize> {
+        let len = len as i64;
+        let (start, end, step) = match *self {
+            Self::Index(index) => (Some(index), Some(index), 1),
+            Self::Range { start, end, step } => (start, end, step),
+        };
+        // 0 based, both inclusive
+        let zero_based = |index: i64| match index {
+            0.. => index - 1,
+            _ => len + index,
+        };
+
+        let (first, bound) = match step {
+            0 => (0, 0),
+            1.. => {
+                let first = start.map_or(0, zero_based);
+                let last = end.map_or(len - 1, zero_based).min(len - 1);
+                // skip ahead to the first index inside the playlist
+                let first = match first {
+                    ..0 => first + (-first + step - 1) / step * step,
+                    _ => first,
+                };
+                (first, last + 1)
+            }
+            _ => {
+                let first = start.map_or(len - 1, zero_based);
+                let last = end.map_or(0, zero_based).max(0);
+                let first = match first - (len - 1) {
+                    excess @ 1.. => first - (excess - step - 1) / -step * -step,
+                    _ => first,
+                };
+                (first, last - 1)
+            }
+        };
+
+        std::iter::successors(Some(first), move |i| i.checked_add(step))
+            .take_while(move |i| match step {
+                0 => false,
+                1.. => *i < bound,
+                _ => *i > bound,
+            })
+            .map(|i| i as usize + 1)
+    }
+}
+
+impl From<i64> for PlaylistItem {
+    fn from(index: i64) -> Self {
+        Self::Index(index)
+    }
+}
+
+impl From<RangeInclusive<i64>> for PlaylistItem {
+    fn from(range: RangeInclusive<i64>) -> Self {
+        Self::range(Some(*range.start()), Some(*range.end()))
+    }
+}
+
+impl From<RangeFrom<i64>> for PlaylistItem {
+    fn from(range: RangeFrom<i64>) -> Self {
+        Self::range(Some(range.start), None)
+    }
+}
+
+impl From<RangeToInclusive<i64>> for PlaylistItem {
+    fn from(range: RangeToInclusive<i64>) -> Self {
+        Self::range(None, Some(range.end))
+    }
+}
+
+impl From<RangeFull> for PlaylistItem {
+    fn from(_: RangeFull) -> Self {
+        Self::range(None, None)
+    }
+}
+
+impl From<Vec<PlaylistItem>> for PlaylistItems {
+    fn from(items: Vec<PlaylistItem>) -> Self {
+        Self(items)
+    }
+}
+
+impl From<PlaylistItems> for String {
+    fn from(items: PlaylistItems) -> Self {
+        items.to_string()
+    }
+}
+
+impl From<&PlaylistItems> for String {
+    fn from(items: &PlaylistItems) -> Self {
+        items.to_string()
+    }
+}
+
+impl Display for PlaylistItems {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, item) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{item}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for PlaylistItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Index(index) => write!(f, "{index}"),
+            Self::Range { start, end, step } => {
+                if let Some(start) = start {
+                    write!(f, "{start}")?;
+                }
+                f.write_str(":")?;
+                if let Some(end) = end {
+                    write!(f, "{end}")?;
+                }
+                if *step != 1 {
+                    write!(f, ":{step}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FromStr for PlaylistItems {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|item| PlaylistItem::from_str(item).map_err(|e| e.replace(s.to_string())))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+impl FromStr for PlaylistItem {
+    type Err = ParseError;
+
+    /// Parses `[START]:[END][:STEP]`, `START-END` or a single index, the same as yt-dlp
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || {
+            ParseError::new(
+                s.to_string(),
+                ParseErrorVariant::InvalidPlaylistItem(s.to_string()),
+            )
+        };
+        let number = |s: &str| {
+            let digits = s.strip_prefix(['+', '-']).unwrap_or(s);
+            match !digits.is_empty() && digits.bytes().all(|c| c.is_ascii_digit()) {
+                true => s.parse::<i64>().map_err(|_| error()),
+                false => Err(error()),
+            }
+        };
+
+        // the start may be signed, so the separator is the first `:` or `-` after it
+        let sign = usize::from(s.starts_with(['+', '-']));
+        let Some(separator) = s[sign..].find([':', '-']).map(|i| i + sign) else {
+            return Ok(Self::Index(number(s)?));
+        };
+
+        let start = match &s[..separator] {
+            "" => None,
+            start => Some(number(start)?),
+        };
+        let (end, step) = match s[separator + 1..].split_once(':') {
+            Some((end, step)) => (end, Some(number(step)?)),
+            None => (&s[separator + 1..], None),
+        };
+        let end = match end {
+            "" | "inf" | "infinite" => None,
+            end => Some(number(end)?),
+        };
+
+        match step {
+            Some(0) => Err(error()),
+            step => Ok(Self::Range {
+                start,
+                end,
+                step: step.unwrap_or(1),
+            }),
+        }
+    }
+}
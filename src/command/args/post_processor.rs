@@ -0,0 +1,551 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{ParseError, ParseErrorVariant};
+
+/// A post-processor to enable, passed to [`Builder::use_postprocessor`](crate::Builder::use_postprocessor),
+/// e.g. `NAME:when=after_move;key=value`
+///
+/// yt-dlp splits the arguments on `;` and `=` without any escaping, so keys cannot contain `=`
+/// and neither keys nor values can contain `;`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostProcessor {
+    /// The case sensitive name of the post-processor
+    pub name: String,
+    /// `when=`, defaults to [`PostProcessorStage::PostProcess`]
+    pub when: Option<PostProcessorStage>,
+    /// `NAME=VALUE` arguments passed to the post-processor
+    pub args: Vec<(String, String)>,
+}
+
+/// When a post-processor is run, the `WHEN` of `--use-postprocessor`, `--exec` and `--print`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PostProcessorStage {
+    /// `pre_process`, after video extraction
+    PreProcess,
+    /// `after_filter`, after the video passes the filters
+    AfterFilter,
+    /// `video`, after `--format`, before `--print` and `--output`
+    Video,
+    /// `before_dl`, before each video download
+    BeforeDownload,
+    /// `post_process`, after each video download
+    PostProcess,
+    /// `after_move`, after moving the video file to its final location
+    AfterMove,
+    /// `after_video`, after downloading and processing all formats of a video
+    AfterVideo,
+    /// `playlist`, at the end of a playlist
+    Playlist,
+}
+
+/// Arguments for a post-processor or the executables it runs, passed to
+/// [`Builder::postprocessor_args`](crate::Builder::postprocessor_args), e.g. `Merger+ffmpeg_i1:-v quiet`
+///
+/// The arguments are shell-quoted when rendered, yt-dlp splits them the same way a POSIX shell
+/// would.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PostProcessorArgs {
+    /// Only give the arguments to this post-processor
+    pub post_processor: Option<PostProcessorName>,
+    /// Only give the arguments to this executable
+    pub executable: Option<Executable>,
+    pub args: Vec<String>,
+}
+
+/// Built-in post-processors accepting `--postprocessor-args`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PostProcessorName {
+    Merger,
+    ModifyChapters,
+    SplitChapters,
+    ExtractAudio,
+    VideoRemuxer,
+    VideoConvertor,
+    Metadata,
+    EmbedSubtitle,
+    EmbedThumbnail,
+    SubtitlesConvertor,
+    ThumbnailsConvertor,
+    FixupStretched,
+    FixupM4a,
+    FixupM3u8,
+    FixupTimestamp,
+    FixupDuration,
+    /// A post-processor from a plugin
+    Other(String),
+}
+
+/// Executables run by post-processors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Executable {
+    AtomicParsley,
+    FFmpeg(Option<ArgPosition>),
+    FFprobe(Option<ArgPosition>),
+}
+
+/// Where the arguments are placed in an ffmpeg or ffprobe command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArgPosition {
+    /// `_i`, before an input file, or only the given input file (counting from 1) if `Some`
+    Input(Option<u32>),
+    /// `_o`, before an output file, or only the given output file (counting from 1) if `Some`
+    Output(Option<u32>),
+}
+
+impl PostProcessor {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            when: None,
+            args: Vec::new(),
+        }
+    }
+
+    pub fn when(mut self, when: PostProcessorStage) -> Self {
+        self.when = Some(when);
+        self
+    }
+
+    pub fn arg(mut self, key: &str, value: impl ToString) -> Self {
+        self.args.push((key.to_string(), value.to_string()));
+        self
+    }
+}
+
+impl PostProcessorStage {
+    const ALL: [Self; 8] = [
+        Self::PreProcess,
+        Self::AfterFilter,
+        Self::Video,
+        Self::BeforeDownload,
+        Self::PostProcess,
+        Self::AfterMove,
+        Self::AfterVideo,
+        Self::Playlist,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::PreProcess => "pre_process",
+            Self::AfterFilter => "after_filter",
+            Self::Video => "video",
+            Self::BeforeDownload => "before_dl",
+            Self::PostProcess => "post_process",
+            Self::AfterMove => "after_move",
+            Self::AfterVideo => "after_video",
+            Self::Playlist => "playlist",
+        }
+    }
+}
+
+impl PostProcessorArgs {
+    /// Arguments for every post-processor and executable without more specific arguments
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn post_processor(mut self, name: PostProcessorName) -> Self {
+        self.post_processor = Some(name);
+        self
+    }
+
+    pub fn executable(mut self, executable: Executable) -> Self {
+        self.executable = Some(executable);
+        self
+    }
+
+    /// Append a single argument, quoted when rendered if needed
+    pub fn arg(mut self, arg: impl ToString) -> Self {
+        self.args.push(arg.to_string());
+        self
+    }
+
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.args
+            .extend(args.into_iter().map(|arg| arg.to_string()));
+        self
+    }
+}
+
+impl PostProcessorName {
+    const BUILT_IN: [Self; 16] = [
+        Self::Merger,
+        Self::ModifyChapters,
+        Self::SplitChapters,
+        Self::ExtractAudio,
+        Self::VideoRemuxer,
+        Self::VideoConvertor,
+        Self::Metadata,
+        Self::EmbedSubtitle,
+        Self::EmbedThumbnail,
+        Self::SubtitlesConvertor,
+        Self::ThumbnailsConvertor,
+        Self::FixupStretched,
+        Self::FixupM4a,
+        Self::FixupM3u8,
+        Self::FixupTimestamp,
+        Self::FixupDuration,
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Merger => "Merger",
+            Self::ModifyChapters => "ModifyChapters",
+            Self::SplitChapters => "SplitChapters",
+            Self::ExtractAudio => "ExtractAudio",
+            Self::VideoRemuxer => "VideoRemuxer",
+            Self::VideoConvertor => "VideoConvertor",
+            Self::Metadata => "Metadata",
+            Self::EmbedSubtitle => "EmbedSubtitle",
+            Self::EmbedThumbnail => "EmbedThumbnail",
+            Self::SubtitlesConvertor => "SubtitlesConvertor",
+            Self::ThumbnailsConvertor => "ThumbnailsConvertor",
+            Self::FixupStretched => "FixupStretched",
+            Self::FixupM4a => "FixupM4a",
+            Self::FixupM3u8 => "FixupM3u8",
+            Self::FixupTimestamp => "FixupTimestamp",
+            Self::FixupDuration => "FixupDuration",
+            Self::Other(name) => name,
+        }
+    }
+}
+
+impl Executable {
+    pub fn ffmpeg() -> Self {
+        Self::FFmpeg(None)
+    }
+
+    pub fn ffprobe() -> Self {
+        Self::FFprobe(None)
+    }
+
+    /// Place the arguments before the input files, or only before input `n` (counting from 1),
+    /// no effect on AtomicParsley
+    pub fn input(self, n: Option<u32>) -> Self {
+        self.position(ArgPosition::Input(n))
+    }
+
+    /// Place the arguments before the output files, or only before output `n` (counting from 1),
+    /// no effect on AtomicParsley
+    pub fn output(self, n: Option<u32>) -> Self {
+        self.position(ArgPosition::Output(n))
+    }
+
+    fn position(self, position: ArgPosition) -> Self {
+        match self {
+            Self::AtomicParsley => self,
+            Self::FFmpeg(_) => Self::FFmpeg(Some(position)),
+            Self::FFprobe(_) => Self::FFprobe(Some(position)),
+        }
+    }
+}
+
+impl From<PostProcessor> for String {
+    fn from(post_processor: PostProcessor) -> Self {
+        post_processor.to_string()
+    }
+}
+
+impl From<&PostProcessor> for String {
+    fn from(post_processor: &PostProcessor) -> Self {
+        post_processor.to_string()
+    }
+}
+
+impl From<PostProcessorArgs> for String {
+    fn from(args: PostProcessorArgs) -> Self {
+        args.to_string()
+    }
+}
+
+impl From<&PostProcessorArgs> for String {
+    fn from(args: &PostProcessorArgs) -> Self {
+        args.to_string()
+    }
+}
+
+impl Display for PostProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)?;
+
+        let when = self.when.iter().map(|when| ("when", when.name()));
+        let args = self
+            .args
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()));
+        for (i, (key, value)) in when.chain(args).enumerate() {
+            f.write_str(if i == 0 { ":" } else { ";" })?;
+            write!(f, "{key}={value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for PostProcessorStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Display for PostProcessorArgs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // without a key, arguments that look like `key:` would be taken as one
+        match (&self.post_processor, &self.executable) {
+            (Some(name), Some(executable)) => write!(f, "{name}+{executable}:")?,
+            (Some(name), None) => write!(f, "{name}:")?,
+            (None, Some(executable)) => write!(f, "{executable}:")?,
+            (None, None) => f.write_str("default:")?,
+        }
+
+        for (i, arg) in self.args.iter().enumerate() {
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(&shell_quote(arg))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for PostProcessorName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Display for Executable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let position = match self {
+            Self::AtomicParsley => return f.write_str("atomicparsley"),
+            Self::FFmpeg(position) => {
+                f.write_str("ffmpeg")?;
+                position
+            }
+            Self::FFprobe(position) => {
+                f.write_str("ffprobe")?;
+                position
+            }
+        };
+
+        let (suffix, n) = match position {
+            Some(ArgPosition::Input(n)) => ("_i", n),
+            Some(ArgPosition::Output(n)) => ("_o", n),
+            None => return Ok(()),
+        };
+        f.write_str(suffix)?;
+        match n {
+            Some(n) => write!(f, "{n}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Quotes an argument like Python's `shlex.quote`
+fn shell_quote(arg: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "_@%+=:,./-".contains(c);
+
+    match !arg.is_empty() && arg.chars().all(safe) {
+        true => arg.to_string(),
+        false => format!("'{}'", arg.replace('\'', r#"'"'"'"#)),
+    }
+}
+
+/// Splits arguments like Python's `shlex.split`, `None` if a quote is not closed
+fn shell_split(s: &str) -> Option<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => args.extend(current.take()),
+            '\\' => {
+                let arg = current.get_or_insert_with(String::new);
+                arg.extend(chars.next());
+            }
+            '\'' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => arg.push(c),
+                    }
+                }
+            }
+            '"' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            c @ ('"' | '\\') => arg.push(c),
+                            c => {
+                                arg.push('\\');
+                                arg.push(c);
+                            }
+                        },
+                        c => arg.push(c),
+                    }
+                }
+            }
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    args.extend(current);
+
+    Some(args)
+}
+
+impl FromStr for PostProcessor {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, args) = match s.split_once(':') {
+            Some((name, "")) => (name, Vec::new()),
+            Some((name, args)) => (name, args.split(';').collect()),
+            None => (s, Vec::new()),
+        };
+        let mut post_processor = Self::new(name);
+
+        for arg in args {
+            let (key, value) = arg.split_once('=').ok_or_else(|| {
+                ParseError::new(
+                    s.to_string(),
+                    ParseErrorVariant::InvalidPostProcessorArg(arg.to_string()),
+                )
+            })?;
+
+            post_processor = match key {
+                "when" => post_processor.when(
+                    PostProcessorStage::from_str(value).map_err(|e| e.replace(s.to_string()))?,
+                ),
+                key => post_processor.arg(key, value),
+            };
+        }
+
+        Ok(post_processor)
+    }
+}
+
+impl FromStr for PostProcessorStage {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|stage| stage.name() == s)
+            .ok_or_else(|| {
+                ParseError::new(
+                    s.to_string(),
+                    ParseErrorVariant::UnknownPostProcessorStage(s.to_string()),
+                )
+            })
+    }
+}
+
+impl FromStr for PostProcessorArgs {
+    type Err = ParseError;
+
+    /// Parses `[NAME[+EXE]:]ARGS`, where NAME can also be an executable, the same as yt-dlp
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_word = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_');
+
+        let (key, args) = match s.split_once(':') {
+            Some((key, args))
+                if key
+                    .split_once('+')
+                    .map_or(is_word(key), |(a, b)| is_word(a) && is_word(b)) =>
+            {
+                (Some(key), args)
+            }
+            _ => (None, s),
+        };
+
+        let (post_processor, executable) = match key {
+            None => (None, None),
+            Some(key) if key.eq_ignore_ascii_case("default") => (None, None),
+            Some(key) => match key.split_once('+') {
+                Some((name, executable)) => (
+                    Some(PostProcessorName::from_str(name)?),
+                    Some(Executable::from_str(executable).map_err(|e| e.replace(s.to_string()))?),
+                ),
+                None => match Executable::from_str(key) {
+                    Ok(executable) => (None, Some(executable)),
+                    Err(_) => (Some(PostProcessorName::from_str(key)?), None),
+                },
+            },
+        };
+
+        let args = shell_split(args)
+            .ok_or_else(|| ParseError::new(s.to_string(), ParseErrorVariant::UnterminatedQuote))?;
+
+        Ok(Self {
+            post_processor,
+            executable,
+            args,
+        })
+    }
+}
+
+impl FromStr for PostProcessorName {
+    type Err = ParseError;
+
+    /// Case insensitive, like yt-dlp, unknown names are plugin post-processors
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::BUILT_IN
+            .into_iter()
+            .find(|name| name.name().eq_ignore_ascii_case(s))
+            .unwrap_or_else(|| Self::Other(s.to_string())))
+    }
+}
+
+impl FromStr for Executable {
+    type Err = ParseError;
+
+    /// Case insensitive, e.g. `AtomicParsley`, `ffmpeg` or `ffprobe_o1`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || {
+            ParseError::new(
+                s.to_string(),
+                ParseErrorVariant::UnknownExecutable(s.to_string()),
+            )
+        };
+        let lowercase = s.to_ascii_lowercase();
+
+        if lowercase == "atomicparsley" {
+            return Ok(Self::AtomicParsley);
+        }
+
+        let (executable, rest) = match lowercase.strip_prefix("ffmpeg") {
+            Some(rest) => (Self::ffmpeg(), rest),
+            None => (
+                Self::ffprobe(),
+                lowercase.strip_prefix("ffprobe").ok_or_else(error)?,
+            ),
+        };
+        if rest.is_empty() {
+            return Ok(executable);
+        }
+
+        let (input, n) = match (rest.strip_prefix("_i"), rest.strip_prefix("_o")) {
+            (Some(n), _) => (true, n),
+            (_, Some(n)) => (false, n),
+            _ => return Err(error()),
+        };
+        let n = match n {
+            "" => None,
+            n => Some(n.parse().map_err(|_| error())?),
+        };
+
+        Ok(match input {
+            true => executable.input(n),
+            false => executable.output(n),
+        })
+    }
+}
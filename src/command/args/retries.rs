@@ -0,0 +1,63 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{ParseError, ParseErrorVariant};
+
+/// Number of retries for [`Builder::retries`](crate::Builder::retries) and the other retry
+/// options
+///
+/// `"infinite"` and other strings yt-dlp accepts are converted with `.parse()` or [`TryFrom`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Retries {
+    Count(u32),
+    /// Retry until it succeeds, `infinite`
+    Infinite,
+}
+
+impl From<u32> for Retries {
+    fn from(count: u32) -> Self {
+        Self::Count(count)
+    }
+}
+
+impl TryFrom<&str> for Retries {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s)
+    }
+}
+
+impl From<Retries> for String {
+    fn from(retries: Retries) -> Self {
+        retries.to_string()
+    }
+}
+
+impl From<&Retries> for String {
+    fn from(retries: &Retries) -> Self {
+        retries.to_string()
+    }
+}
+
+impl Display for Retries {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Count(count) => write!(f, "{count}"),
+            Self::Infinite => f.write_str("infinite"),
+        }
+    }
+}
+
+impl FromStr for Retries {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "inf" | "infinite" => Ok(Self::Infinite),
+            s => s
+                .parse()
+                .map(Self::Count)
+                .map_err(|_| ParseError::new(s.to_string(), ParseErrorVariant::InvalidRetries)),
+        }
+    }
+}
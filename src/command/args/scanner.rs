@@ -0,0 +1,60 @@
+/// Minimal cursor shared by the parsers of the argument mini-languages
+pub(crate) struct Scanner<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(s: &'a str) -> Self {
+        Self { s, pos: 0 }
+    }
+
+    pub fn input(&self) -> &'a str {
+        self.s
+    }
+
+    /// Byte offset of the next character
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.s[self.pos..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.s.len()
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    pub fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    /// Consumes `prefix` if the remaining input starts with it
+    pub fn eat(&mut self, prefix: &str) -> bool {
+        let matched = self.rest().starts_with(prefix);
+        if matched {
+            self.pos += prefix.len();
+        }
+        matched
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// Consumes characters as long as `f` holds, returning them
+    pub fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(&f) {
+            self.bump();
+        }
+        &self.s[start..self.pos]
+    }
+}
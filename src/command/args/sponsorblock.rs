@@ -0,0 +1,416 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{ParseError, ParseErrorVariant};
+
+use super::{OutputTemplate, TemplateField, TemplatePart};
+
+/// SponsorBlock segment categories, see <https://wiki.sponsor.ajay.app/w/Segment_Categories>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SponsorBlockCategory {
+    /// `sponsor`
+    Sponsor,
+    /// `intro`
+    Intro,
+    /// `outro`
+    Outro,
+    /// `selfpromo`
+    SelfPromo,
+    /// `preview`
+    Preview,
+    /// `filler`
+    Filler,
+    /// `interaction`
+    Interaction,
+    /// `music_offtopic`
+    MusicOfftopic,
+    /// `poi_highlight`, only for `--sponsorblock-mark`
+    PoiHighlight,
+    /// `chapter`, only for `--sponsorblock-mark`
+    Chapter,
+    /// `all`
+    All,
+    /// `default`, same as `all` for `--sponsorblock-mark` and `all,-filler` for
+    /// `--sponsorblock-remove`
+    Default,
+}
+
+impl SponsorBlockCategory {
+    const ALL: [Self; 12] = [
+        Self::Sponsor,
+        Self::Intro,
+        Self::Outro,
+        Self::SelfPromo,
+        Self::Preview,
+        Self::Filler,
+        Self::Interaction,
+        Self::MusicOfftopic,
+        Self::PoiHighlight,
+        Self::Chapter,
+        Self::All,
+        Self::Default,
+    ];
+
+    /// Name of the category as used by yt-dlp
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Sponsor => "sponsor",
+            Self::Intro => "intro",
+            Self::Outro => "outro",
+            Self::SelfPromo => "selfpromo",
+            Self::Preview => "preview",
+            Self::Filler => "filler",
+            Self::Interaction => "interaction",
+            Self::MusicOfftopic => "music_offtopic",
+            Self::PoiHighlight => "poi_highlight",
+            Self::Chapter => "chapter",
+            Self::All => "all",
+            Self::Default => "default",
+        }
+    }
+
+    /// Whether yt-dlp accepts the category for `--sponsorblock-remove`, every category except
+    /// `poi_highlight` and `chapter`, which are not segments that can be cut out
+    pub fn is_removable(&self) -> bool {
+        !matches!(self, Self::PoiHighlight | Self::Chapter)
+    }
+}
+
+/// Categories for [`Builder::sponsorblock_mark`](crate::Builder::sponsorblock_mark), e.g.
+/// `all,-preview`, see [`SponsorBlockRemoval`] for `--sponsorblock-remove`
+///
+/// yt-dlp applies the entries in order, so excluding a category only has an effect after it has
+/// been included.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SponsorBlockCategories(Vec<CategorySelection>);
+
+/// One comma separated entry of [`SponsorBlockCategories`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CategorySelection {
+    pub category: SponsorBlockCategory,
+    /// Remove the category from the ones selected so far, the `-` prefix
+    pub exclude: bool,
+}
+
+impl SponsorBlockCategories {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn include(mut self, category: SponsorBlockCategory) -> Self {
+        self.0.push(CategorySelection {
+            category,
+            exclude: false,
+        });
+        self
+    }
+
+    pub fn exclude(mut self, category: SponsorBlockCategory) -> Self {
+        self.0.push(CategorySelection {
+            category,
+            exclude: true,
+        });
+        self
+    }
+
+    pub fn selections(&self) -> &[CategorySelection] {
+        &self.0
+    }
+}
+
+impl From<SponsorBlockCategory> for SponsorBlockCategories {
+    fn from(category: SponsorBlockCategory) -> Self {
+        Self::new().include(category)
+    }
+}
+
+impl From<SponsorBlockCategory> for String {
+    fn from(category: SponsorBlockCategory) -> Self {
+        category.name().to_string()
+    }
+}
+
+impl From<SponsorBlockCategories> for String {
+    fn from(categories: SponsorBlockCategories) -> Self {
+        categories.to_string()
+    }
+}
+
+impl From<&SponsorBlockCategories> for String {
+    fn from(categories: &SponsorBlockCategories) -> Self {
+        categories.to_string()
+    }
+}
+
+impl Display for SponsorBlockCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Display for SponsorBlockCategories {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, selection) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{selection}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for CategorySelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.exclude {
+            f.write_str("-")?;
+        }
+        f.write_str(self.category.name())
+    }
+}
+
+impl FromStr for SponsorBlockCategory {
+    type Err = ParseError;
+
+    /// Case insensitive, like yt-dlp
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                ParseError::new(
+                    s.to_string(),
+                    ParseErrorVariant::UnknownSponsorBlockCategory(s.to_string()),
+                )
+            })
+    }
+}
+
+impl FromStr for SponsorBlockCategories {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|selection| {
+                let (exclude, category) = match selection.strip_prefix('-') {
+                    Some(category) => (true, category),
+                    None => (false, selection),
+                };
+
+                SponsorBlockCategory::from_str(category)
+                    .map(|category| CategorySelection { category, exclude })
+                    .map_err(|e| e.replace(s.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+/// Categories for [`Builder::sponsorblock_remove`](crate::Builder::sponsorblock_remove), e.g.
+/// `all,-filler`
+///
+/// The same as [`SponsorBlockCategories`] without `poi_highlight` and `chapter`, which yt-dlp
+/// rejects for `--sponsorblock-remove`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SponsorBlockRemoval(SponsorBlockCategories);
+
+impl SponsorBlockRemoval {
+    pub fn categories(&self) -> &SponsorBlockCategories {
+        &self.0
+    }
+}
+
+impl TryFrom<SponsorBlockCategories> for SponsorBlockRemoval {
+    type Error = ParseError;
+
+    /// Fails if the categories include or exclude `poi_highlight` or `chapter`
+    fn try_from(categories: SponsorBlockCategories) -> Result<Self, Self::Error> {
+        match categories
+            .selections()
+            .iter()
+            .find(|selection| !selection.category.is_removable())
+        {
+            Some(selection) => Err(ParseError::new(
+                categories.to_string(),
+                ParseErrorVariant::UnremovableSponsorBlockCategory(
+                    selection.category.name().to_string(),
+                ),
+            )),
+            None => Ok(Self(categories)),
+        }
+    }
+}
+
+impl TryFrom<SponsorBlockCategory> for SponsorBlockRemoval {
+    type Error = ParseError;
+
+    fn try_from(category: SponsorBlockCategory) -> Result<Self, Self::Error> {
+        Self::try_from(SponsorBlockCategories::from(category))
+    }
+}
+
+impl From<SponsorBlockRemoval> for String {
+    fn from(removal: SponsorBlockRemoval) -> Self {
+        removal.to_string()
+    }
+}
+
+impl From<&SponsorBlockRemoval> for String {
+    fn from(removal: &SponsorBlockRemoval) -> Self {
+        removal.to_string()
+    }
+}
+
+impl Display for SponsorBlockRemoval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for SponsorBlockRemoval {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(SponsorBlockCategories::from_str(s)?).map_err(|e| e.replace(s.to_string()))
+    }
+}
+
+/// Fields available in [`SponsorBlockChapterTitle`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChapterTitleField {
+    /// `start_time`
+    StartTime,
+    /// `end_time`
+    EndTime,
+    /// `category`
+    Category,
+    /// `categories`, a list
+    Categories,
+    /// `name`
+    Name,
+    /// `category_names`, a list
+    CategoryNames,
+}
+
+impl ChapterTitleField {
+    const ALL: [Self; 6] = [
+        Self::StartTime,
+        Self::EndTime,
+        Self::Category,
+        Self::Categories,
+        Self::Name,
+        Self::CategoryNames,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::StartTime => "start_time",
+            Self::EndTime => "end_time",
+            Self::Category => "category",
+            Self::Categories => "categories",
+            Self::Name => "name",
+            Self::CategoryNames => "category_names",
+        }
+    }
+}
+
+/// Template for the title of chapters created by `--sponsorblock-mark`, passed to
+/// [`Builder::sponsorblock_chapter_title`](crate::Builder::sponsorblock_chapter_title)
+///
+/// An output template that may only use the [`ChapterTitleField`]s, yt-dlp leaves any other field
+/// unavailable without an error.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SponsorBlockChapterTitle(OutputTemplate);
+
+impl SponsorBlockChapterTitle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn literal(self, text: &str) -> Self {
+        Self(self.0.literal(text))
+    }
+
+    /// Append a field, lists are joined with `, ` (the `l` conversion) and other fields are
+    /// converted with `s`
+    pub fn field(self, field: ChapterTitleField) -> Self {
+        let conversion = match field {
+            ChapterTitleField::Categories | ChapterTitleField::CategoryNames => 'l',
+            _ => 's',
+        };
+        Self(
+            self.0
+                .field(TemplateField::new(field.name()).conversion(conversion)),
+        )
+    }
+
+    pub fn template(&self) -> &OutputTemplate {
+        &self.0
+    }
+}
+
+impl TryFrom<OutputTemplate> for SponsorBlockChapterTitle {
+    type Error = ParseError;
+
+    /// Fails if the template uses a field other than the [`ChapterTitleField`]s
+    fn try_from(template: OutputTemplate) -> Result<Self, Self::Error> {
+        let is_available = |path: &str| {
+            let name = path.split('.').next().unwrap_or_default();
+            ChapterTitleField::ALL
+                .iter()
+                .any(|field| field.name() == name)
+        };
+
+        for part in template.parts() {
+            let TemplatePart::Field(field) = part else {
+                continue;
+            };
+
+            let paths = field.alternatives.iter().flat_map(|expression| {
+                std::iter::once(expression.path.as_str()).chain(
+                    expression
+                        .maths
+                        .iter()
+                        .map(|(_, operand)| operand.as_str())
+                        .filter(|operand| operand.parse::<f64>().is_err()),
+                )
+            });
+            for path in paths {
+                if !is_available(path) {
+                    return Err(ParseError::new(
+                        template.to_string(),
+                        ParseErrorVariant::UnknownChapterTitleField(path.to_string()),
+                    ));
+                }
+            }
+        }
+
+        Ok(Self(template))
+    }
+}
+
+impl From<SponsorBlockChapterTitle> for String {
+    fn from(title: SponsorBlockChapterTitle) -> Self {
+        title.to_string()
+    }
+}
+
+impl From<&SponsorBlockChapterTitle> for String {
+    fn from(title: &SponsorBlockChapterTitle) -> Self {
+        title.to_string()
+    }
+}
+
+impl Display for SponsorBlockChapterTitle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for SponsorBlockChapterTitle {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(OutputTemplate::from_str(s)?).map_err(|e| e.replace(s.to_string()))
+    }
+}
@@ -0,0 +1,231 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{ParseError, ParseErrorVariant};
+
+/// A date for [`Builder::date`](crate::Builder::date), [`Builder::datebefore`](crate::Builder::datebefore)
+/// and [`Builder::dateafter`](crate::Builder::dateafter), e.g. `20240131` or `today-2weeks`
+///
+/// yt-dlp only accepts offsets into the past for these options, `today+1day` is rejected, see
+/// [`DateBase::ago`].
+/// Fallible conversions from `chrono::NaiveDate` and `time::Date` are available with the
+/// `chrono` and `time` features.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YtDate {
+    /// `YYYYMMDD`
+    Absolute { year: u16, month: u8, day: u8 },
+    /// `now`, `today` or `yesterday`, optionally followed by e.g. `-3months`
+    Relative {
+        base: DateBase,
+        ago: Option<(u32, DateUnit)>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateBase {
+    /// `now`, same as `today`
+    Now,
+    /// `today`
+    Today,
+    /// `yesterday`
+    Yesterday,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateUnit {
+    /// `day`
+    Day,
+    /// `week`
+    Week,
+    /// `month`
+    Month,
+    /// `year`
+    Year,
+}
+
+impl DateBase {
+    const ALL: [Self; 3] = [Self::Now, Self::Today, Self::Yesterday];
+
+    /// The date `amount` units before the base, e.g. `DateBase::Today.ago(2, DateUnit::Week)`
+    /// for `today-2weeks`
+    pub fn ago(self, amount: u32, unit: DateUnit) -> YtDate {
+        YtDate::Relative {
+            base: self,
+            ago: Some((amount, unit)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Now => "now",
+            Self::Today => "today",
+            Self::Yesterday => "yesterday",
+        }
+    }
+}
+
+impl DateUnit {
+    const ALL: [Self; 4] = [Self::Day, Self::Week, Self::Month, Self::Year];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Year => "year",
+        }
+    }
+}
+
+impl YtDate {
+    /// An absolute date, `None` if the date does not exist or the year is not 4 digits long
+    pub fn ymd(year: u16, month: u8, day: u8) -> Option<Self> {
+        let days = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if year.is_multiple_of(4)
+                && (!year.is_multiple_of(100) || year.is_multiple_of(400)) =>
+            {
+                29
+            }
+            2 => 28,
+            _ => return None,
+        };
+
+        ((1000..=9999).contains(&year) && (1..=days).contains(&day)).then_some(Self::Absolute {
+            year,
+            month,
+            day,
+        })
+    }
+
+    pub fn now() -> Self {
+        Self::relative(DateBase::Now)
+    }
+
+    pub fn today() -> Self {
+        Self::relative(DateBase::Today)
+    }
+
+    pub fn yesterday() -> Self {
+        Self::relative(DateBase::Yesterday)
+    }
+
+    fn relative(base: DateBase) -> Self {
+        Self::Relative { base, ago: None }
+    }
+}
+
+impl From<DateBase> for YtDate {
+    fn from(base: DateBase) -> Self {
+        Self::relative(base)
+    }
+}
+
+impl From<YtDate> for String {
+    fn from(date: YtDate) -> Self {
+        date.to_string()
+    }
+}
+
+impl From<&YtDate> for String {
+    fn from(date: &YtDate) -> Self {
+        date.to_string()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::NaiveDate> for YtDate {
+    type Error = ParseError;
+
+    /// Fails for years outside of 1000 to 9999, since yt-dlp only accepts 4 digit years
+    fn try_from(date: chrono::NaiveDate) -> Result<Self, Self::Error> {
+        use chrono::Datelike;
+
+        match u16::try_from(date.year()) {
+            Ok(year @ 1000..=9999) => Ok(Self::Absolute {
+                year,
+                month: date.month() as u8,
+                day: date.day() as u8,
+            }),
+            _ => Err(ParseError::new(
+                date.to_string(),
+                ParseErrorVariant::InvalidDate,
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<time::Date> for YtDate {
+    type Error = ParseError;
+
+    /// Fails for years outside of 1000 to 9999, since yt-dlp only accepts 4 digit years
+    fn try_from(date: time::Date) -> Result<Self, Self::Error> {
+        match u16::try_from(date.year()) {
+            Ok(year @ 1000..=9999) => Ok(Self::Absolute {
+                year,
+                month: date.month() as u8,
+                day: date.day(),
+            }),
+            _ => Err(ParseError::new(
+                date.to_string(),
+                ParseErrorVariant::InvalidDate,
+            )),
+        }
+    }
+}
+
+impl Display for YtDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Absolute { year, month, day } => write!(f, "{year:04}{month:02}{day:02}"),
+            Self::Relative { base, ago } => {
+                f.write_str(base.name())?;
+                match ago {
+                    Some((1, unit)) => write!(f, "-1{}", unit.name()),
+                    Some((amount, unit)) => write!(f, "-{amount}{}s", unit.name()),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for YtDate {
+    type Err = ParseError;
+
+    /// Parses the dates yt-dlp accepts for `--date`, `YYYYMMDD` or
+    /// `(now|today|yesterday)[-N(day|week|month|year)[s]]`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseError::new(s.to_string(), ParseErrorVariant::InvalidDate);
+
+        if s.len() == 8 && s.bytes().all(|c| c.is_ascii_digit()) {
+            return Self::ymd(
+                s[..4].parse().unwrap(),
+                s[4..6].parse().unwrap(),
+                s[6..].parse().unwrap(),
+            )
+            .ok_or_else(error);
+        }
+
+        let (base, rest) = DateBase::ALL
+            .into_iter()
+            .find_map(|base| s.strip_prefix(base.name()).map(|rest| (base, rest)))
+            .ok_or_else(error)?;
+        if rest.is_empty() {
+            return Ok(Self::relative(base));
+        }
+
+        let rest = rest.strip_prefix('-').ok_or_else(error)?;
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let amount = rest[..digits].parse().map_err(|_| error())?;
+        let unit = &rest[digits..];
+        let unit = unit.strip_suffix('s').unwrap_or(unit);
+        let unit = DateUnit::ALL
+            .into_iter()
+            .find(|u| u.name() == unit)
+            .ok_or_else(error)?;
+
+        Ok(base.ago(amount, unit))
+    }
+}
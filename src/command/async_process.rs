@@ -0,0 +1,223 @@
+use std::{
+    io,
+    pin::Pin,
+    process::{ExitStatus, Stdio},
+    str::FromStr,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_core::Stream;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, BufReader},
+    process::{Child, Command},
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+};
+
+use crate::{Builder, Output, ParseError};
+
+#[cfg(unix)]
+use super::process::send_signal;
+use super::{process::take_line, Execution};
+
+/// A yt-dlp process running on the tokio runtime, returned by
+/// [`Builder::spawn_async`](crate::Builder::spawn_async)
+///
+/// The handle is a [`Stream`] of lines from stdout and stderr in the order they arrive.
+/// Dropping the handle kills yt-dlp if it is still running.
+pub struct AsyncDownloadHandle {
+    child: Child,
+    lines: UnboundedReceiver<String>,
+}
+
+impl AsyncDownloadHandle {
+    fn new(mut child: Child) -> Self {
+        let (sender, lines) = mpsc::unbounded_channel();
+
+        if let Some(stdout) = child.stdout.take() {
+            tokio::spawn(forward_lines(stdout, sender.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(forward_lines(stderr, sender));
+        }
+
+        Self { child, lines }
+    }
+
+    /// OS-assigned process identifier, `None` once the process has been awaited
+    pub fn id(&self) -> Option<u32> {
+        self.child.id()
+    }
+
+    /// Exit status of the process if it has already exited, without waiting
+    pub fn try_status(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.child.try_wait()
+    }
+
+    /// Wait for the process to exit, parsing every line not yet taken from the stream
+    pub async fn wait(mut self) -> io::Result<Execution> {
+        let mut lines = Vec::new();
+        while let Some(line) = self.lines.recv().await {
+            lines.push(Output::from_str(&line));
+        }
+
+        let mut execution = Execution::new(self.child.wait().await?);
+        lines.into_iter().for_each(|line| execution.push(line));
+
+        Ok(execution)
+    }
+
+    /// Ask yt-dlp to stop by sending SIGINT, which lets it clean up `.part` files
+    ///
+    /// On platforms without signals the process is killed instead.
+    pub fn interrupt(&mut self) -> io::Result<()> {
+        if self.child.try_wait()?.is_some() {
+            return Ok(());
+        }
+
+        #[cfg(unix)]
+        return match self.child.id() {
+            Some(pid) => send_signal(pid, libc::SIGINT),
+            None => Ok(()),
+        };
+        #[cfg(not(unix))]
+        return self.child.start_kill();
+    }
+
+    /// Kill the process immediately, leaving partial files behind
+    pub async fn kill(&mut self) -> io::Result<()> {
+        self.child.kill().await
+    }
+
+    /// Suspend the process with SIGSTOP
+    #[cfg(unix)]
+    pub fn pause(&mut self) -> io::Result<()> {
+        match self.child.try_wait()? {
+            None => self
+                .child
+                .id()
+                .map_or(Ok(()), |pid| send_signal(pid, libc::SIGSTOP)),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Continue a process suspended by [`pause`](Self::pause) with SIGCONT
+    #[cfg(unix)]
+    pub fn resume(&mut self) -> io::Result<()> {
+        match self.child.try_wait()? {
+            None => self
+                .child
+                .id()
+                .map_or(Ok(()), |pid| send_signal(pid, libc::SIGCONT)),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// [`interrupt`](Self::interrupt) the process and wait for it to exit, killing it if it is
+    /// still running after `timeout`
+    ///
+    /// The process is always reaped, so no zombie is left behind.
+    pub async fn cancel(mut self, timeout: Duration) -> io::Result<Execution> {
+        #[cfg(unix)]
+        self.resume()?;
+        self.interrupt()?;
+
+        let mut lines = Vec::new();
+        let collect = async {
+            while let Some(line) = self.lines.recv().await {
+                lines.push(Output::from_str(&line));
+            }
+            // closed pipes do not mean the process has exited
+            self.child.wait().await.map(|_| ())
+        };
+        match tokio::time::timeout(timeout, collect).await {
+            Ok(result) => result?,
+            Err(_) => self.child.kill().await?,
+        }
+
+        let mut execution = Execution::new(self.child.wait().await?);
+        // processes started by yt-dlp may still hold the pipes open, so only take what is there
+        while let Ok(line) = self.lines.try_recv() {
+            lines.push(Output::from_str(&line));
+        }
+        lines.into_iter().for_each(|line| execution.push(line));
+
+        Ok(execution)
+    }
+}
+
+impl Drop for AsyncDownloadHandle {
+    /// Kill the process if it is still running, tokio reaps it in the background
+    fn drop(&mut self) {
+        if let Ok(None) = self.child.try_wait() {
+            let _ = self.child.start_kill();
+        }
+    }
+}
+
+impl Stream for AsyncDownloadHandle {
+    type Item = Result<Output, ParseError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.lines
+            .poll_recv(cx)
+            .map(|line| line.map(|line| Output::from_str(&line)))
+    }
+}
+
+async fn forward_lines(stream: impl AsyncRead + Unpin, sender: UnboundedSender<String>) {
+    let mut reader = BufReader::new(stream);
+    let mut line = Vec::new();
+
+    loop {
+        let buf = match reader.fill_buf().await {
+            Ok([]) | Err(_) => break,
+            Ok(buf) => buf,
+        };
+
+        let (consumed, ended) = take_line(buf, &mut line);
+        reader.consume(consumed);
+
+        if ended && !line.is_empty() {
+            if sender
+                .send(String::from_utf8_lossy(&line).into_owned())
+                .is_err()
+            {
+                return;
+            }
+            line.clear();
+        }
+    }
+
+    if !line.is_empty() {
+        let _ = sender.send(String::from_utf8_lossy(&line).into_owned());
+    }
+}
+
+impl Builder {
+    /// Start yt-dlp on the tokio runtime with stdout and stderr captured
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn spawn_async(&mut self) -> io::Result<AsyncDownloadHandle> {
+        // tokio takes the command by value, converting it keeps every setting made through
+        // `command_mut`, and the builder gets it back afterwards
+        let program = self.command_mut().get_program().to_owned();
+        let command = std::mem::replace(self.command_mut(), std::process::Command::new(program));
+        let mut async_command = Command::from(command);
+
+        let child = async_command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn();
+        *self.command_mut() = async_command.into_std();
+
+        Ok(AsyncDownloadHandle::new(child?))
+    }
+
+    /// Start yt-dlp on the tokio runtime and wait for it to exit
+    pub async fn run_async(&mut self) -> io::Result<Execution> {
+        self.spawn_async()?.wait().await
+    }
+}
@@ -1,4 +1,12 @@
-use std::process::Command;
+use std::{
+    io,
+    process::{Command, Stdio},
+    str::FromStr,
+};
+
+use crate::{Info, RunError, PROGRESS_PREFIX};
+
+use super::{DownloadHandle, Execution};
 
 pub struct Builder(Command);
 
@@ -10,4 +18,49 @@ impl Builder {
     pub fn command_mut(&mut self) -> &mut Command {
         &mut self.0
     }
+
+    /// Print download progress as one marked JSON object per line, so that it is parsed into
+    /// [`Progress`](crate::Progress) rather than [`Message`](crate::Message)
+    pub fn structured_progress(&mut self) -> &mut Self {
+        self.newline()
+            .progress_template(&format!("download:{PROGRESS_PREFIX}%(progress)j"))
+    }
+
+    /// Start yt-dlp with stdout and stderr captured
+    pub fn spawn(&mut self) -> io::Result<DownloadHandle> {
+        self.0
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        Ok(DownloadHandle::new(self.0.spawn()?))
+    }
+
+    /// Start yt-dlp and wait for it to exit
+    pub fn run(&mut self) -> io::Result<Execution> {
+        self.spawn()?.wait()
+    }
+
+    /// Run yt-dlp with `--dump-single-json` and parse the metadata it prints, without downloading
+    pub fn fetch_info(&mut self) -> Result<Info, RunError> {
+        // the builder may be reused, so only add the flag the first time
+        if !self
+            .command_mut()
+            .get_args()
+            .any(|arg| arg == "--dump-single-json")
+        {
+            self.dump_single_json();
+        }
+
+        let output = self.command_mut().stdin(Stdio::null()).output()?;
+
+        if !output.status.success() {
+            let mut execution = Execution::new(output.status);
+            execution.push_bytes(&output.stderr);
+            return Err(RunError::Failed(execution));
+        }
+
+        Ok(Info::from_str(
+            String::from_utf8_lossy(&output.stdout).trim(),
+        )?)
+    }
 }
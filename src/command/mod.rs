@@ -1,4 +1,12 @@
+mod args;
+#[cfg(feature = "tokio")]
+mod async_process;
 mod builder;
 mod options;
+mod process;
 
+pub use args::*;
+#[cfg(feature = "tokio")]
+pub use async_process::AsyncDownloadHandle;
 pub use builder::Builder;
+pub use process::{DownloadHandle, Execution};
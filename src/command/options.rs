@@ -1,4 +1,6 @@
-use super::Builder;
+use std::time::Duration;
+
+use super::{Builder, ByteSize, Retries};
 
 /// General Options
 impl Builder {
@@ -176,9 +178,9 @@ impl Builder {
     }
 
     /// Time to wait before giving up, in seconds
-    pub fn socket_timeout(&mut self, seconds: &str) -> &mut Self {
+    pub fn socket_timeout(&mut self, seconds: Duration) -> &mut Self {
         self.command_mut().arg("--socket-timeout");
-        self.command_mut().arg(seconds);
+        self.command_mut().arg(seconds.as_secs_f64().to_string());
         self
     }
 
@@ -235,51 +237,51 @@ impl Builder {
     }
 
     /// Comma separated playlist_index of the items to download. You can specify a range using "[START]:[STOP][:STEP]". For backward compatibility, START-STOP is also supported. Use negative indices to count from the right and negative STEP to download in reverse order. E.g. "-I 1:3,7,-5::2" used on a playlist of size 15 will download the items at index 1,2,3,7,11,13,15
-    pub fn playlist_items(&mut self, itemspec: &str) -> &mut Self {
+    pub fn playlist_items(&mut self, itemspec: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--playlist-items");
-        self.command_mut().arg(itemspec);
+        self.command_mut().arg::<String>(itemspec.into());
         self
     }
 
     /// Abort download if filesize is smaller than SIZE, e.g. 50k or 44.6M
-    pub fn min_filesize(&mut self, size: &str) -> &mut Self {
+    pub fn min_filesize(&mut self, size: impl Into<ByteSize>) -> &mut Self {
         self.command_mut().arg("--min-filesize");
-        self.command_mut().arg(size);
+        self.command_mut().arg(size.into().to_string());
         self
     }
 
     /// Abort download if filesize is larger than SIZE, e.g. 50k or 44.6M
-    pub fn max_filesize(&mut self, size: &str) -> &mut Self {
+    pub fn max_filesize(&mut self, size: impl Into<ByteSize>) -> &mut Self {
         self.command_mut().arg("--max-filesize");
-        self.command_mut().arg(size);
+        self.command_mut().arg(size.into().to_string());
         self
     }
 
     /// Download only videos uploaded on this date. The date can be "YYYYMMDD" or in the format [now|today|yesterday][- N[day|week|month|year]]. E.g. "--date today-2weeks" downloads only videos uploaded on the same day two weeks ago
-    pub fn date(&mut self, date: &str) -> &mut Self {
+    pub fn date(&mut self, date: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--date");
-        self.command_mut().arg(date);
+        self.command_mut().arg::<String>(date.into());
         self
     }
 
     /// Download only videos uploaded on or before this date. The date formats accepted is the same as --date
-    pub fn datebefore(&mut self, date: &str) -> &mut Self {
+    pub fn datebefore(&mut self, date: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--datebefore");
-        self.command_mut().arg(date);
+        self.command_mut().arg::<String>(date.into());
         self
     }
 
     /// Download only videos uploaded on or after this date. The date formats accepted is the same as --date
-    pub fn dateafter(&mut self, date: &str) -> &mut Self {
+    pub fn dateafter(&mut self, date: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--dateafter");
-        self.command_mut().arg(date);
+        self.command_mut().arg::<String>(date.into());
         self
     }
 
     /// Generic video filter. Any "OUTPUT TEMPLATE" field can be compared with a number or a string using the operators defined in "Filtering Formats". You can also simply specify a field to match if the field is present, use "!field" to check if the field is not present, and "&" to check multiple conditions. Use a "\" to escape "&" or quotes if needed. If used multiple times, the filter matches if at least one of the conditions is met. E.g. --match-filter !is_live --match-filter "like_count>?100 & description~='(?i)\bcats \& dogs\b'" matches only videos that are not live OR those that have a like count more than 100 (or the like field is not available) and also has a description that contains the phrase "cats & dogs" (caseless). Use "--match-filter -" to interactively ask whether to download each video
-    pub fn match_filters(&mut self, filter: &str) -> &mut Self {
+    pub fn match_filters(&mut self, filter: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--match-filters");
-        self.command_mut().arg(filter);
+        self.command_mut().arg::<String>(filter.into());
         self
     }
 
@@ -290,9 +292,9 @@ impl Builder {
     }
 
     /// Same as "--match-filters" but stops the download process when a video is rejected
-    pub fn break_match_filters(&mut self, filter: &str) -> &mut Self {
+    pub fn break_match_filters(&mut self, filter: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--break-match-filters");
-        self.command_mut().arg(filter);
+        self.command_mut().arg::<String>(filter.into());
         self
     }
 
@@ -315,9 +317,9 @@ impl Builder {
     }
 
     /// Download only videos suitable for the given age
-    pub fn age_limit(&mut self, years: &str) -> &mut Self {
+    pub fn age_limit(&mut self, years: u32) -> &mut Self {
         self.command_mut().arg("--age-limit");
-        self.command_mut().arg(years);
+        self.command_mut().arg(years.to_string());
         self
     }
 
@@ -335,9 +337,9 @@ impl Builder {
     }
 
     /// Abort after downloading NUMBER files
-    pub fn max_downloads(&mut self, number: &str) -> &mut Self {
+    pub fn max_downloads(&mut self, number: u32) -> &mut Self {
         self.command_mut().arg("--max-downloads");
-        self.command_mut().arg(number);
+        self.command_mut().arg(number.to_string());
         self
     }
 
@@ -376,44 +378,44 @@ impl Builder {
 /// Download Options
 impl Builder {
     /// Number of fragments of a dash/hlsnative video that should be downloaded concurrently (default is 1)
-    pub fn concurrent_fragments(&mut self, n: &str) -> &mut Self {
+    pub fn concurrent_fragments(&mut self, n: u32) -> &mut Self {
         self.command_mut().arg("--concurrent-fragments");
-        self.command_mut().arg(n);
+        self.command_mut().arg(n.to_string());
         self
     }
 
     /// Maximum download rate in bytes per second, e.g. 50K or 4.2M
-    pub fn limit_rate(&mut self, rate: &str) -> &mut Self {
+    pub fn limit_rate(&mut self, rate: impl Into<ByteSize>) -> &mut Self {
         self.command_mut().arg("--limit-rate");
-        self.command_mut().arg(rate);
+        self.command_mut().arg(rate.into().to_string());
         self
     }
 
     /// Minimum download rate in bytes per second below which throttling is assumed and the video data is re-extracted, e.g. 100K
-    pub fn throttled_rate(&mut self, rate: &str) -> &mut Self {
+    pub fn throttled_rate(&mut self, rate: impl Into<ByteSize>) -> &mut Self {
         self.command_mut().arg("--throttled-rate");
-        self.command_mut().arg(rate);
+        self.command_mut().arg(rate.into().to_string());
         self
     }
 
     /// Number of retries (default is 10), or "infinite"
-    pub fn retries(&mut self, retries: &str) -> &mut Self {
+    pub fn retries(&mut self, retries: impl Into<Retries>) -> &mut Self {
         self.command_mut().arg("--retries");
-        self.command_mut().arg(retries);
+        self.command_mut().arg(retries.into().to_string());
         self
     }
 
     /// Number of times to retry on file access error (default is 3), or "infinite"
-    pub fn file_access_retries(&mut self, retries: &str) -> &mut Self {
+    pub fn file_access_retries(&mut self, retries: impl Into<Retries>) -> &mut Self {
         self.command_mut().arg("--file-access-retries");
-        self.command_mut().arg(retries);
+        self.command_mut().arg(retries.into().to_string());
         self
     }
 
     /// Number of retries for a fragment (default is 10), or "infinite" (DASH, hlsnative and ISM)
-    pub fn fragment_retries(&mut self, retries: &str) -> &mut Self {
+    pub fn fragment_retries(&mut self, retries: impl Into<Retries>) -> &mut Self {
         self.command_mut().arg("--fragment-retries");
-        self.command_mut().arg(retries);
+        self.command_mut().arg(retries.into().to_string());
         self
     }
 
@@ -449,9 +451,9 @@ impl Builder {
     }
 
     /// Size of download buffer, e.g. 1024 or 16K (default is 1024)
-    pub fn buffer_size(&mut self, size: &str) -> &mut Self {
+    pub fn buffer_size(&mut self, size: impl Into<ByteSize>) -> &mut Self {
         self.command_mut().arg("--buffer-size");
-        self.command_mut().arg(size);
+        self.command_mut().arg(size.into().to_string());
         self
     }
 
@@ -468,9 +470,9 @@ impl Builder {
     }
 
     /// Size of a chunk for chunk-based HTTP downloading, e.g. 10485760 or 10M (default is disabled). May be useful for bypassing bandwidth throttling imposed by a webserver (experimental)
-    pub fn http_chunk_size(&mut self, size: &str) -> &mut Self {
+    pub fn http_chunk_size(&mut self, size: impl Into<ByteSize>) -> &mut Self {
         self.command_mut().arg("--http-chunk-size");
-        self.command_mut().arg(size);
+        self.command_mut().arg(size.into().to_string());
         self
     }
 
@@ -511,9 +513,9 @@ impl Builder {
     }
 
     /// Download only chapters that match the regular expression. A "*" prefix denotes time-range instead of chapter. Negative timestamps are calculated from the end. "*from-url" can be used to download between the "start_time" and "end_time" extracted from the URL. Needs ffmpeg. This option can be used multiple times to download multiple sections, e.g. --download-sections "*10:15-inf" --download-sections "intro"
-    pub fn download_sections(&mut self, regex: &str) -> &mut Self {
+    pub fn download_sections(&mut self, regex: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--download-sections");
-        self.command_mut().arg(regex);
+        self.command_mut().arg::<String>(regex.into());
         self
     }
 
@@ -555,9 +557,9 @@ impl Builder {
     }
 
     /// Output filename template; see "OUTPUT TEMPLATE" for details
-    pub fn output(&mut self, typestemplate: &str) -> &mut Self {
+    pub fn output(&mut self, typestemplate: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--output");
-        self.command_mut().arg(typestemplate);
+        self.command_mut().arg::<String>(typestemplate.into());
         self
     }
 
@@ -1034,16 +1036,16 @@ impl Builder {
 /// Video Format Options
 impl Builder {
     /// Video format code, see "FORMAT SELECTION" for more details
-    pub fn format(&mut self, format: &str) -> &mut Self {
+    pub fn format(&mut self, format: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--format");
-        self.command_mut().arg(format);
+        self.command_mut().arg::<String>(format.into());
         self
     }
 
     /// Sort the formats by the fields given, see "Sorting Formats" for more details
-    pub fn format_sort(&mut self, sortorder: &str) -> &mut Self {
+    pub fn format_sort(&mut self, sortorder: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--format-sort");
-        self.command_mut().arg(sortorder);
+        self.command_mut().arg::<String>(sortorder.into());
         self
     }
 
@@ -1282,16 +1284,16 @@ impl Builder {
     }
 
     /// Format to convert the audio to when -x is used. (currently supported: best (default), aac, alac, flac, m4a, mp3, opus, vorbis, wav). You can specify multiple rules using similar syntax as --remux-video
-    pub fn audio_format(&mut self, format: &str) -> &mut Self {
+    pub fn audio_format(&mut self, format: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--audio-format");
-        self.command_mut().arg(format);
+        self.command_mut().arg::<String>(format.into());
         self
     }
 
     /// Specify ffmpeg audio quality to use when converting the audio with -x. Insert a value between 0 (best) and 10 (worst) for VBR or a specific bitrate like 128K (default 5)
-    pub fn audio_quality(&mut self, quality: &str) -> &mut Self {
+    pub fn audio_quality(&mut self, quality: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--audio-quality");
-        self.command_mut().arg(quality);
+        self.command_mut().arg::<String>(quality.into());
         self
     }
 
@@ -1310,9 +1312,9 @@ impl Builder {
     }
 
     /// Give these arguments to the postprocessors. Specify the postprocessor/executable name and the arguments separated by a colon ":" to give the argument to the specified postprocessor/executable. Supported PP are: Merger, ModifyChapters, SplitChapters, ExtractAudio, VideoRemuxer, VideoConvertor, Metadata, EmbedSubtitle, EmbedThumbnail, SubtitlesConvertor, ThumbnailsConvertor, FixupStretched, FixupM4a, FixupM3u8, FixupTimestamp and FixupDuration. The supported executables are: AtomicParsley, FFmpeg and FFprobe. You can also specify "PP+EXE:ARGS" to give the arguments to the specified executable only when being used by the specified postprocessor. Additionally, for ffmpeg/ffprobe, "_i"/"_o" can be appended to the prefix optionally followed by a number to pass the argument before the specified input/output file, e.g. --ppa "Merger+ffmpeg_i1:-v quiet". You can use this option multiple times to give different arguments to different postprocessors. (Alias: --ppa)
-    pub fn postprocessor_args(&mut self, nameargs: &str) -> &mut Self {
+    pub fn postprocessor_args(&mut self, nameargs: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--postprocessor-args");
-        self.command_mut().arg(nameargs);
+        self.command_mut().arg::<String>(nameargs.into());
         self
     }
 
@@ -1506,9 +1508,9 @@ impl Builder {
     }
 
     ///  The (case sensitive) name of plugin postprocessors to be enabled, and (optionally) arguments to be passed to it, separated by a colon ":". ARGS are a semicolon ";" delimited list of NAME=VALUE. The "when" argument determines when the postprocessor is invoked. It can be one of "pre_process" (after video extraction), "after_filter" (after video passes filter), "video" (after --format; before --print/--output), "before_dl" (before each video download), "post_process" (after each video download; default), "after_move" (after moving video file to its final locations), "after_video" (after downloading and processing all formats of a video), or "playlist" (at end of playlist). This option can be used multiple times to add different postprocessors
-    pub fn use_postprocessor(&mut self, nameargs: &str) -> &mut Self {
+    pub fn use_postprocessor(&mut self, nameargs: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--use-postprocessor");
-        self.command_mut().arg(nameargs);
+        self.command_mut().arg::<String>(nameargs.into());
         self
     }
 }
@@ -1516,23 +1518,23 @@ impl Builder {
 /// SponsorBlock Options   
 impl Builder {
     /// SponsorBlock categories to create chapters for, separated by commas. Available categories are sponsor, intro, outro, selfpromo, preview, filler, interaction, music_offtopic, poi_highlight, chapter, all and default (=all). You can prefix the category with a "-" to exclude it. See [1] for description of the categories. E.g. --sponsorblock-mark all,-preview [1] https:/ /wiki.sponsor.ajay.app/w/Segment_Categories
-    pub fn sponsorblock_mark(&mut self, cats: &str) -> &mut Self {
+    pub fn sponsorblock_mark(&mut self, cats: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--sponsorblock-mark");
-        self.command_mut().arg(cats);
+        self.command_mut().arg::<String>(cats.into());
         self
     }
 
     /// SponsorBlock categories to be removed from the video file, separated by commas. If a category is present in both mark and remove, remove takes precedence. The syntax and available categories are the same as for --sponsorblock-mark except that "default" refers to "all,-filler" and poi_highlight, chapter are not available
-    pub fn sponsorblock_remove(&mut self, cats: &str) -> &mut Self {
+    pub fn sponsorblock_remove(&mut self, cats: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--sponsorblock-remove");
-        self.command_mut().arg(cats);
+        self.command_mut().arg::<String>(cats.into());
         self
     }
 
     ///  An output template for the title of the SponsorBlock chapters created by --sponsorblock-mark. The only available fields are start_time, end_time, category, categories, name, category_names. Defaults to "[SponsorBlock]: %(category_names)l"
-    pub fn sponsorblock_chapter_title(&mut self, template: &str) -> &mut Self {
+    pub fn sponsorblock_chapter_title(&mut self, template: impl Into<String>) -> &mut Self {
         self.command_mut().arg("--sponsorblock-chapter-title");
-        self.command_mut().arg(template);
+        self.command_mut().arg::<String>(template.into());
         self
     }
 
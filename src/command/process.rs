@@ -0,0 +1,303 @@
+use std::{
+    io::{self, BufRead, BufReader, Read},
+    process::{Child, ExitStatus},
+    str::FromStr,
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    DebugHeader, LifecycleEvent, Message, MessageType, Output, ParseError, YtDlpError, YtDlpWarning,
+};
+
+/// A running yt-dlp process, returned by [`Builder::spawn`](crate::Builder::spawn)
+///
+/// Iterating over the handle yields lines from stdout and stderr in the order they arrive.
+/// Dropping the handle kills yt-dlp if it is still running.
+pub struct DownloadHandle {
+    child: Child,
+    lines: Receiver<String>,
+}
+
+impl DownloadHandle {
+    pub(crate) fn new(mut child: Child) -> Self {
+        let (sender, lines) = mpsc::channel();
+
+        if let Some(stdout) = child.stdout.take() {
+            let sender = sender.clone();
+            thread::spawn(move || forward_lines(stdout, sender));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            thread::spawn(move || forward_lines(stderr, sender));
+        }
+
+        Self { child, lines }
+    }
+
+    /// OS-assigned process identifier of the yt-dlp process
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Exit status of the process if it has already exited, without blocking
+    pub fn try_status(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.child.try_wait()
+    }
+
+    /// Wait for the process to exit, parsing every line not yet taken from the iterator
+    pub fn wait(mut self) -> io::Result<Execution> {
+        let lines = self.by_ref().collect::<Vec<_>>();
+        let mut execution = Execution::new(self.child.wait()?);
+        lines.into_iter().for_each(|line| execution.push(line));
+
+        Ok(execution)
+    }
+
+    /// Ask yt-dlp to stop by sending SIGINT, which lets it clean up `.part` files
+    ///
+    /// On platforms without signals the process is killed instead.
+    pub fn interrupt(&mut self) -> io::Result<()> {
+        if self.child.try_wait()?.is_some() {
+            return Ok(());
+        }
+
+        #[cfg(unix)]
+        return send_signal(self.child.id(), libc::SIGINT);
+        #[cfg(not(unix))]
+        return self.child.kill();
+    }
+
+    /// Kill the process immediately, leaving partial files behind
+    pub fn kill(&mut self) -> io::Result<()> {
+        self.child.kill()
+    }
+
+    /// Suspend the process with SIGSTOP
+    #[cfg(unix)]
+    pub fn pause(&mut self) -> io::Result<()> {
+        if self.child.try_wait()?.is_some() {
+            return Ok(());
+        }
+        send_signal(self.child.id(), libc::SIGSTOP)
+    }
+
+    /// Continue a process suspended by [`pause`](Self::pause) with SIGCONT
+    #[cfg(unix)]
+    pub fn resume(&mut self) -> io::Result<()> {
+        if self.child.try_wait()?.is_some() {
+            return Ok(());
+        }
+        send_signal(self.child.id(), libc::SIGCONT)
+    }
+
+    /// [`interrupt`](Self::interrupt) the process and wait for it to exit, killing it if it is
+    /// still running after `timeout`
+    ///
+    /// The process is always reaped, so no zombie is left behind.
+    pub fn cancel(mut self, timeout: Duration) -> io::Result<Execution> {
+        #[cfg(unix)]
+        self.resume()?;
+        self.interrupt()?;
+
+        let deadline = Instant::now() + timeout;
+        let mut lines = Vec::new();
+        loop {
+            match self
+                .lines
+                .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            {
+                Ok(line) => lines.push(Output::from_str(&line)),
+                Err(RecvTimeoutError::Timeout) => {
+                    self.kill()?;
+                    break;
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    // closed pipes do not mean the process has exited
+                    while self.child.try_wait()?.is_none() {
+                        if Instant::now() >= deadline {
+                            self.kill()?;
+                            break;
+                        }
+                        thread::sleep(Duration::from_millis(10));
+                    }
+                    break;
+                }
+            }
+        }
+
+        let mut execution = Execution::new(self.child.wait()?);
+        // processes started by yt-dlp may still hold the pipes open, so only take what is there
+        lines.extend(self.lines.try_iter().map(|line| Output::from_str(&line)));
+        lines.into_iter().for_each(|line| execution.push(line));
+
+        Ok(execution)
+    }
+}
+
+impl Drop for DownloadHandle {
+    /// Kill and reap the process if it is still running, e.g. after breaking out of the iterator
+    fn drop(&mut self) {
+        if let Ok(None) = self.child.try_wait() {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
+
+impl Iterator for DownloadHandle {
+    type Item = Result<Output, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines.recv().ok().map(|line| Output::from_str(&line))
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn send_signal(pid: u32, signal: libc::c_int) -> io::Result<()> {
+    if unsafe { libc::kill(pid as libc::pid_t, signal) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Sends every non-empty line of `stream` to `sender`, treating both `\n` and `\r` as line
+/// endings so progress redraws arrive as separate lines
+fn forward_lines(stream: impl Read, sender: Sender<String>) {
+    let mut reader = BufReader::new(stream);
+    let mut line = Vec::new();
+
+    loop {
+        let buf = match reader.fill_buf() {
+            Ok([]) => break,
+            Ok(buf) => buf,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        };
+
+        let (consumed, ended) = take_line(buf, &mut line);
+        reader.consume(consumed);
+
+        if ended && !line.is_empty() {
+            if sender
+                .send(String::from_utf8_lossy(&line).into_owned())
+                .is_err()
+            {
+                return;
+            }
+            line.clear();
+        }
+    }
+
+    if !line.is_empty() {
+        let _ = sender.send(String::from_utf8_lossy(&line).into_owned());
+    }
+}
+
+/// Moves bytes from `buf` into `line` up to the first `\n` or `\r`, returning how many bytes were
+/// consumed and whether the line ended
+pub(crate) fn take_line(buf: &[u8], line: &mut Vec<u8>) -> (usize, bool) {
+    match buf.iter().position(|b| matches!(b, b'\n' | b'\r')) {
+        Some(i) => {
+            line.extend_from_slice(&buf[..i]);
+            (i + 1, true)
+        }
+        None => {
+            line.extend_from_slice(buf);
+            (buf.len(), false)
+        }
+    }
+}
+
+/// Result of a finished yt-dlp process
+#[derive(Debug)]
+pub struct Execution {
+    status: ExitStatus,
+    output: Vec<Output>,
+    unparsed: Vec<ParseError>,
+}
+
+impl Execution {
+    pub(crate) fn new(status: ExitStatus) -> Self {
+        Self {
+            status,
+            output: Vec::new(),
+            unparsed: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, line: Result<Output, ParseError>) {
+        match line {
+            Ok(output) => self.output.push(output),
+            Err(e) => self.unparsed.push(e),
+        }
+    }
+
+    /// Parses output captured all at once rather than streamed
+    pub(crate) fn push_bytes(&mut self, bytes: &[u8]) {
+        String::from_utf8_lossy(bytes)
+            .split(['\n', '\r'])
+            .filter(|line| !line.is_empty())
+            .for_each(|line| self.push(Output::from_str(line)));
+    }
+
+    pub fn status(&self) -> ExitStatus {
+        self.status
+    }
+
+    /// Whether yt-dlp exited with status 0
+    pub fn success(&self) -> bool {
+        self.status.success()
+    }
+
+    /// Every successfully parsed line, in the order it was printed
+    pub fn output(&self) -> &[Output] {
+        &self.output
+    }
+
+    pub fn messages(&self) -> impl Iterator<Item = &Message> {
+        self.output.iter().filter_map(|output| match output {
+            Output::Message(message) => Some(message),
+            Output::Progress(_) => None,
+        })
+    }
+
+    /// Environment printed with [`Builder::verbose`](crate::Builder::verbose), `None` if yt-dlp
+    /// was not run with it
+    pub fn debug_header(&self) -> Option<DebugHeader> {
+        DebugHeader::from_messages(self.messages())
+    }
+
+    /// Steps of the download reported by neutral messages, e.g. the destination of each file
+    pub fn lifecycle_events(&self) -> impl Iterator<Item = LifecycleEvent> + '_ {
+        self.messages().filter_map(LifecycleEvent::from_message)
+    }
+
+    /// Messages reported by yt-dlp as `ERROR:`
+    pub fn errors(&self) -> impl Iterator<Item = &Message> {
+        self.messages()
+            .filter(|message| message.message_type() == MessageType::Error)
+    }
+
+    /// [`errors`](Self::errors) classified by their text
+    pub fn ytdlp_errors(&self) -> impl Iterator<Item = YtDlpError> + '_ {
+        self.errors().map(YtDlpError::from)
+    }
+
+    /// Messages reported by yt-dlp as `WARNING:`
+    pub fn warnings(&self) -> impl Iterator<Item = &Message> {
+        self.messages()
+            .filter(|message| message.message_type() == MessageType::Warning)
+    }
+
+    /// [`warnings`](Self::warnings) classified by their text
+    pub fn ytdlp_warnings(&self) -> impl Iterator<Item = YtDlpWarning> + '_ {
+        self.warnings().map(YtDlpWarning::from)
+    }
+
+    /// Lines that could not be parsed into an [`Output`]
+    pub fn unparsed(&self) -> &[ParseError] {
+        &self.unparsed
+    }
+}
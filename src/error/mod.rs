@@ -1,3 +1,5 @@
 mod parse_error;
+mod run_error;
 
 pub use parse_error::{ParseError, ParseErrorVariant};
+pub use run_error::RunError;
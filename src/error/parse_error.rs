@@ -1,3 +1,6 @@
+use std::{error::Error, fmt::Display};
+
+#[derive(Debug)]
 pub struct ParseError {
     content: String,
     error: ParseErrorVariant,
@@ -12,18 +15,116 @@ impl ParseError {
         self.content = content;
         self
     }
+
+    /// The line (or part of a line) that failed to parse
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub fn error(&self) -> &ParseErrorVariant {
+        &self.error
+    }
 }
 
-pub enum ParseErrorVariant {
-    /// There are non whitespace characters between type of a message and the tag of a message,
-    /// e.g.
-    /// - `ERROR: [youtube] error message` is okay, but
-    /// - `ERROR: text [youtube] error message` is not
-    NonspaceBeforeTags,
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} in `{}`", self.error, self.content)
+    }
+}
+
+impl Error for ParseError {}
 
+#[derive(Debug)]
+pub enum ParseErrorVariant {
     /// The message type cannot be parsed,
     /// e.g.
     /// - `ERROR: [youtube] error message` is okay, but
     /// - `DONNOWHA: [youtube] error message` is not
     UnknownMessageType(String),
+
+    /// A line starting with `{` is not a progress object produced by `%(progress)j`,
+    /// the reason is given by the JSON parser
+    InvalidProgress(String),
+
+    /// The output of `--dump-json` or `--dump-single-json` is not valid info JSON,
+    /// the reason is given by the JSON parser
+    InvalidInfo(String),
+
+    /// A format selector passed to `--format` is malformed, `position` is the byte offset
+    /// in the selector where `expected` was not found
+    InvalidFormatSelector {
+        position: usize,
+        expected: &'static str,
+    },
+
+    /// A `--format-sort` field is not one of the documented fields,
+    /// e.g. `res` is okay, but `resolution` is not
+    UnknownSortField(String),
+
+    /// A `--format-sort` field is followed by `:` or `~` without a value, e.g. `res:`
+    MissingSortValue(String),
+
+    /// An output template passed to `--output` is malformed, `position` is the byte offset
+    /// in the template where `expected` was not found
+    InvalidOutputTemplate {
+        position: usize,
+        expected: &'static str,
+    },
+
+    /// A condition in a `--match-filters` filter is not a field name, `!` followed by a field
+    /// name, or a comparison, e.g. `like_count>?100` is okay, but `like_count%100` is not
+    InvalidMatchCondition(String),
+
+    /// A size is not a number optionally followed by one of `KMGTPEZY`,
+    /// e.g. `10M` and `4.2k` are okay, but `10MB` is not
+    InvalidByteSize,
+
+    /// A number of retries is neither a non-negative integer nor `infinite`
+    InvalidRetries,
+
+    /// A date is neither `YYYYMMDD` of an existing date nor a relative date, or a converted
+    /// date is outside of years 1000 to 9999,
+    /// e.g. `today-2weeks` is okay, but `today+2weeks` and `20240230` are not
+    InvalidDate,
+
+    /// An entry of a `--playlist-items` spec is not an index or a range with a non-zero step,
+    /// e.g. `-5::2` is okay, but `1::0` and `1:3:` are not
+    InvalidPlaylistItem(String),
+
+    /// A `--download-sections` section starting with `*` is not a time range,
+    /// e.g. `*10:15-inf` is okay, but `*10:15` is not
+    InvalidTimeRange,
+
+    /// A SponsorBlock category is not one of the documented categories,
+    /// e.g. `selfpromo` is okay, but `self_promo` is not
+    UnknownSponsorBlockCategory(String),
+
+    /// A `--sponsorblock-remove` category is only available for `--sponsorblock-mark`,
+    /// e.g. `sponsor` is okay, but `poi_highlight` and `chapter` are not
+    UnremovableSponsorBlockCategory(String),
+
+    /// A `--sponsorblock-chapter-title` template uses a field that is not available for
+    /// SponsorBlock chapters, e.g. `category_names` is okay, but `title` is not
+    UnknownChapterTitleField(String),
+
+    /// An argument of a `--use-postprocessor` post-processor is not `NAME=VALUE`
+    InvalidPostProcessorArg(String),
+
+    /// The `when` of a post-processor is not one of the documented stages,
+    /// e.g. `after_move` is okay, but `after-move` is not
+    UnknownPostProcessorStage(String),
+
+    /// A `--postprocessor-args` executable is not AtomicParsley, ffmpeg or ffprobe, optionally
+    /// followed by `_i` or `_o` and a number for the last two
+    UnknownExecutable(String),
+
+    /// Arguments contain a quote that is not closed
+    UnterminatedQuote,
+
+    /// An `--audio-format` target is not one of the supported formats,
+    /// e.g. `aac>m4a` is okay, but `aac>ogg` is not
+    UnknownAudioFormat(String),
+
+    /// An `--audio-quality` is neither a VBR level from 0 to 10 nor a bitrate such as `192K`
+    InvalidAudioQuality,
 }
@@ -0,0 +1,46 @@
+use std::{error::Error, fmt::Display, io};
+
+use crate::{Execution, ParseError};
+
+/// Failure of a yt-dlp invocation that is expected to produce a result
+#[derive(Debug)]
+pub enum RunError {
+    /// yt-dlp could not be started or waited on
+    Io(io::Error),
+    /// yt-dlp exited with a non-zero status, its stderr is parsed into the [`Execution`]
+    Failed(Execution),
+    /// yt-dlp succeeded but its output could not be parsed
+    Parse(ParseError),
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "failed to run yt-dlp: {e}"),
+            Self::Failed(execution) => write!(f, "yt-dlp exited with {}", execution.status()),
+            Self::Parse(e) => write!(f, "failed to parse yt-dlp output: {e}"),
+        }
+    }
+}
+
+impl Error for RunError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Failed(_) => None,
+            Self::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for RunError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ParseError> for RunError {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
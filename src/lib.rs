@@ -3,5 +3,6 @@ mod client;
 mod command;
 mod error;
 
+pub use bindings::*;
 pub use command::*;
 pub use error::*;
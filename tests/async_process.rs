@@ -0,0 +1,141 @@
+#![cfg(all(unix, feature = "tokio"))]
+
+mod common;
+
+use std::{future::poll_fn, os::unix::process::ExitStatusExt, pin::Pin, time::Duration};
+
+use futures_core::Stream;
+use yt_dlp::{Builder, MessageType, Output};
+
+#[tokio::test]
+async fn run_async_collects_output() {
+    let ytdlp = common::fake_ytdlp(
+        "run_async_collects_output",
+        r#"echo "[youtube] abc: Downloading webpage"
+echo "ERROR: [youtube] abc: Video unavailable" >&2
+exit 1"#,
+    );
+
+    let execution = Builder::new(ytdlp.to_str().unwrap())
+        .run_async()
+        .await
+        .unwrap();
+
+    assert!(!execution.success());
+    assert_eq!(execution.output().len(), 2);
+    assert_eq!(execution.errors().count(), 1);
+}
+
+#[tokio::test]
+async fn spawn_async_keeps_command_settings() {
+    let ytdlp = common::fake_ytdlp(
+        "spawn_async_keeps_command_settings",
+        r#"echo "[env] ${HOME:-cleared} $YT_DLP_RS_TEST""#,
+    );
+
+    let mut builder = Builder::new(ytdlp.to_str().unwrap());
+    builder
+        .command_mut()
+        .env_clear()
+        .env("YT_DLP_RS_TEST", "kept");
+
+    for _ in 0..2 {
+        let execution = builder.run_async().await.unwrap();
+        assert!(matches!(
+            execution.output(),
+            [Output::Message(message)] if message.content() == "cleared kept"
+        ));
+    }
+}
+
+#[tokio::test]
+async fn spawn_async_streams_lines() {
+    let ytdlp = common::fake_ytdlp(
+        "spawn_async_streams_lines",
+        r#"echo "[youtube] abc: Downloading webpage"
+sleep 0.1
+echo "WARNING: [youtube] abc: nsig extraction failed" >&2
+sleep 0.1
+echo 'yt-dlp-rs:progress:{"status": "finished", "filename": "x.mp4", "total_bytes": 1, "speed": null, "_speed_str": "", "_total_bytes_str": "", "_elapsed_str": "", "_percent_str": "100.0%", "_default_template": ""}'"#,
+    );
+
+    let mut handle = Builder::new(ytdlp.to_str().unwrap()).spawn_async().unwrap();
+
+    let mut lines = Vec::new();
+    while let Some(line) = poll_fn(|cx| Pin::new(&mut handle).poll_next(cx)).await {
+        lines.push(line.unwrap());
+    }
+
+    assert!(matches!(
+        lines.as_slice(),
+        [
+            Output::Message(neutral),
+            Output::Message(warning),
+            Output::Progress(_),
+        ] if neutral.message_type() == MessageType::Neutral
+            && warning.message_type() == MessageType::Warning
+    ));
+    assert!(handle.wait().await.unwrap().success());
+}
+
+#[tokio::test]
+async fn cancel_async_interrupts_gracefully() {
+    let ytdlp = common::fake_ytdlp(
+        "cancel_async_interrupts_gracefully",
+        r#"trap 'echo "ERROR: Interrupted by user" >&2; exit 1' INT
+echo "[youtube] abc: Downloading webpage"
+while true; do sleep 0.05; done"#,
+    );
+
+    let mut handle = Builder::new(ytdlp.to_str().unwrap()).spawn_async().unwrap();
+    assert!(poll_fn(|cx| Pin::new(&mut handle).poll_next(cx))
+        .await
+        .is_some());
+
+    let execution = handle.cancel(Duration::from_secs(5)).await.unwrap();
+    assert_eq!(execution.status().code(), Some(1));
+    assert_eq!(
+        execution.errors().next().unwrap().content(),
+        "Interrupted by user"
+    );
+}
+
+#[tokio::test]
+async fn cancel_async_kills_after_pipes_are_closed() {
+    let ytdlp = common::fake_ytdlp(
+        "cancel_async_kills_after_pipes_are_closed",
+        "trap '' INT\nexec >&- 2>&-\nwhile true; do sleep 0.05; done",
+    );
+
+    let handle = Builder::new(ytdlp.to_str().unwrap()).spawn_async().unwrap();
+    // there is no output to wait for, give the script time to set up the trap
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let execution = handle.cancel(Duration::from_millis(200)).await.unwrap();
+    assert_eq!(execution.status().signal(), Some(libc::SIGKILL));
+}
+
+#[tokio::test]
+async fn drop_async_kills_the_process() {
+    let ytdlp = common::fake_ytdlp(
+        "drop_async_kills_the_process",
+        "while true; do echo line; sleep 0.05; done",
+    );
+
+    let mut handle = Builder::new(ytdlp.to_str().unwrap()).spawn_async().unwrap();
+    assert!(poll_fn(|cx| Pin::new(&mut handle).poll_next(cx))
+        .await
+        .is_some());
+    let pid = handle.id().unwrap();
+    drop(handle);
+
+    // tokio reaps the killed process in the background, signal 0 also finds zombies
+    let exists = || unsafe { libc::kill(pid as libc::pid_t, 0) } == 0;
+    for _ in 0..100 {
+        if !exists() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(!exists());
+}
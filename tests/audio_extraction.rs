@@ -0,0 +1,108 @@
+use std::str::FromStr;
+
+use yt_dlp::{
+    AudioExtraction, AudioFormat, AudioFormatRule, AudioFormats, AudioQuality, Builder,
+    ParseErrorVariant,
+};
+
+fn args(builder: &mut Builder) -> Vec<String> {
+    builder
+        .command_mut()
+        .get_args()
+        .map(|arg| arg.to_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn builds_format_rules() {
+    let formats = AudioFormats::new()
+        .then(AudioFormatRule::new(AudioFormat::M4a).source("aac"))
+        .then(AudioFormatRule::new(AudioFormat::Best).source("opus"))
+        .then(AudioFormat::Mp3);
+
+    assert_eq!(formats.to_string(), "aac>m4a/opus>best/mp3");
+}
+
+#[test]
+fn parses_format_rules() {
+    let formats = AudioFormats::from_str("aac > m4a/MP3").unwrap();
+    assert_eq!(
+        formats.rules(),
+        [
+            AudioFormatRule {
+                source: Some("aac".to_string()),
+                target: AudioFormat::M4a,
+            },
+            AudioFormatRule::new(AudioFormat::Mp3),
+        ]
+    );
+    assert_eq!(formats.to_string(), "aac>m4a/mp3");
+
+    let error = AudioFormats::from_str("aac>m4a/ogg").unwrap_err();
+    assert!(matches!(
+        error.error(),
+        ParseErrorVariant::UnknownAudioFormat(format) if format == "ogg"
+    ));
+    assert_eq!(error.content(), "aac>m4a/ogg");
+    assert!(AudioFormats::from_str("mp3/").is_err());
+    assert!(AudioFormats::from_str(">mp3").is_err());
+}
+
+#[test]
+fn parses_qualities() {
+    assert_eq!(AudioQuality::from_str("0").unwrap(), AudioQuality::Vbr(0));
+    assert_eq!(AudioQuality::from_str("10").unwrap(), AudioQuality::Vbr(10));
+    assert_eq!(
+        AudioQuality::from_str("192K").unwrap(),
+        AudioQuality::Bitrate(192)
+    );
+    assert_eq!(
+        AudioQuality::from_str("128").unwrap(),
+        AudioQuality::Bitrate(128)
+    );
+    assert_eq!(AudioQuality::Bitrate(192).to_string(), "192K");
+
+    for quality in ["best", "-1", "", "1.5M"] {
+        assert!(matches!(
+            AudioQuality::from_str(quality).unwrap_err().error(),
+            ParseErrorVariant::InvalidAudioQuality
+        ));
+    }
+
+    assert_eq!(AudioQuality::vbr(11), None);
+    assert_eq!(AudioQuality::bitrate(8), None);
+    assert_eq!(AudioQuality::bitrate(320), Some(AudioQuality::Bitrate(320)));
+}
+
+#[test]
+fn applies_extraction_to_builder() {
+    let extraction = AudioExtraction::new()
+        .format(
+            AudioFormats::new()
+                .then(AudioFormatRule::new(AudioFormat::M4a).source("aac"))
+                .then(AudioFormat::Mp3),
+        )
+        .quality(AudioQuality::bitrate(96).unwrap())
+        .keep_video();
+
+    let mut builder = Builder::new("yt-dlp");
+    builder.audio_extraction(&extraction);
+    assert_eq!(
+        args(&mut builder),
+        [
+            "--extract-audio",
+            "--audio-format",
+            "aac>m4a/mp3",
+            "--audio-quality",
+            "96K",
+            "--keep-video"
+        ]
+    );
+
+    let mut builder = Builder::new("yt-dlp");
+    builder.audio_extraction(&AudioExtraction::new().format(AudioFormat::Opus));
+    assert_eq!(
+        args(&mut builder),
+        ["--extract-audio", "--audio-format", "opus"]
+    );
+}
@@ -0,0 +1,115 @@
+use std::{str::FromStr, time::Duration};
+
+use yt_dlp::{Builder, ByteSize, ParseErrorVariant, Retries};
+
+#[test]
+fn renders_sizes() {
+    assert_eq!(ByteSize::new(0).to_string(), "0");
+    assert_eq!(ByteSize::new(1000).to_string(), "1000");
+    assert_eq!(ByteSize::kib(50).to_string(), "50K");
+    assert_eq!(ByteSize::mib(10).to_string(), "10M");
+    assert_eq!(ByteSize::mib(1536).to_string(), "1536M");
+    assert_eq!(ByteSize::gib(2048).to_string(), "2T");
+    assert_eq!(ByteSize::gib(u64::MAX).bytes(), u64::MAX);
+}
+
+#[test]
+fn parses_sizes() {
+    assert_eq!(ByteSize::from_str("1024").unwrap(), ByteSize::kib(1));
+    assert_eq!(ByteSize::from_str("50k").unwrap(), ByteSize::kib(50));
+    assert_eq!(ByteSize::from_str("10M").unwrap(), ByteSize::mib(10));
+    assert_eq!(
+        ByteSize::from_str("4.2M").unwrap().bytes(),
+        (4.2 * 1024. * 1024.) as u64
+    );
+
+    for size in ["10MB", "M", "1e3", ".5K", "5.K", "-1", "", "99999Y"] {
+        let error = ByteSize::from_str(size).unwrap_err();
+        assert!(matches!(error.error(), ParseErrorVariant::InvalidByteSize));
+        assert_eq!(error.content(), size);
+    }
+}
+
+#[test]
+fn parses_retries() {
+    assert_eq!(Retries::from_str("10").unwrap(), Retries::Count(10));
+    assert_eq!(Retries::from_str("inf").unwrap(), Retries::Infinite);
+    assert_eq!(Retries::from_str("infinite").unwrap(), Retries::Infinite);
+    assert!(matches!(
+        Retries::from_str("forever").unwrap_err().error(),
+        ParseErrorVariant::InvalidRetries
+    ));
+    assert_eq!(Retries::Infinite.to_string(), "infinite");
+    assert_eq!(Retries::try_from("infinite").unwrap(), Retries::Infinite);
+}
+
+#[test]
+fn strings_convert_to_typed_options() {
+    let mut builder = Builder::new("yt-dlp");
+    builder
+        .retries("infinite".parse::<Retries>().unwrap())
+        .min_filesize(ByteSize::try_from("50k").unwrap())
+        .max_filesize("1.5G".parse::<ByteSize>().unwrap());
+
+    let args = builder
+        .command_mut()
+        .get_args()
+        .map(|arg| arg.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        args,
+        [
+            "--retries",
+            "infinite",
+            "--min-filesize",
+            "50K",
+            "--max-filesize",
+            "1536M"
+        ]
+    );
+    assert!(ByteSize::try_from("50kb").is_err());
+}
+
+#[test]
+fn typed_options_as_arguments() {
+    let mut builder = Builder::new("yt-dlp");
+    builder
+        .concurrent_fragments(4)
+        .limit_rate(ByteSize::kib(500))
+        .retries(Retries::Infinite)
+        .fragment_retries(3)
+        .max_filesize(ByteSize::gib(1))
+        .buffer_size(16384)
+        .socket_timeout(Duration::from_millis(1500))
+        .age_limit(18)
+        .max_downloads(1);
+
+    let args = builder
+        .command_mut()
+        .get_args()
+        .map(|arg| arg.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        args,
+        [
+            "--concurrent-fragments",
+            "4",
+            "--limit-rate",
+            "500K",
+            "--retries",
+            "infinite",
+            "--fragment-retries",
+            "3",
+            "--max-filesize",
+            "1G",
+            "--buffer-size",
+            "16K",
+            "--socket-timeout",
+            "1.5",
+            "--age-limit",
+            "18",
+            "--max-downloads",
+            "1"
+        ]
+    );
+}
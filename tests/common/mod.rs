@@ -0,0 +1,14 @@
+#![allow(dead_code)]
+
+use std::{fs, os::unix::fs::PermissionsExt, path::PathBuf};
+
+/// Writes an executable shell script standing in for yt-dlp and returns its path
+pub fn fake_ytdlp(name: &str, body: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("yt-dlp-rs-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let path = dir.join(name);
+    fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
@@ -0,0 +1,101 @@
+use std::str::FromStr;
+
+use yt_dlp::{DebugHeader, Dependency, Message};
+
+const VERBOSE: &str = include_str!("fixtures/verbose.txt");
+
+fn header(lines: &str) -> Option<DebugHeader> {
+    let messages = lines
+        .lines()
+        .map(|line| Message::from_str(line).unwrap())
+        .collect::<Vec<_>>();
+    DebugHeader::from_messages(&messages)
+}
+
+#[test]
+fn parses_header() {
+    let header = header(VERBOSE).unwrap();
+
+    assert_eq!(
+        header.command_line,
+        [
+            "-v",
+            "--proxy",
+            "socks5://127.0.0.1:1080",
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+        ]
+    );
+    assert_eq!(header.version, "2024.08.06");
+    assert_eq!(header.channel.as_deref(), Some("stable"));
+    assert_eq!(header.origin.as_deref(), Some("yt-dlp/yt-dlp"));
+    assert_eq!(header.git_head.as_deref(), Some("4d9231208"));
+    assert_eq!(header.variant.as_deref(), Some("pip"));
+    assert_eq!(header.python_version.as_deref(), Some("3.12.4"));
+    assert_eq!(
+        header.platform.as_deref(),
+        Some("Linux-6.9.7-arch1-1-x86_64-with-glibc2.39")
+    );
+    assert_eq!(
+        header.proxies.get("all").map(String::as_str),
+        Some("socks5://127.0.0.1:1080")
+    );
+    assert_eq!(
+        header.request_handlers,
+        ["urllib", "requests", "websockets"]
+    );
+    assert_eq!(
+        header.plugin_directories,
+        ["/home/user/.config/yt-dlp/plugins/example/yt_dlp_plugins"]
+    );
+    assert_eq!(header.extractors, Some(1838));
+}
+
+#[test]
+fn parses_dependencies() {
+    let header = header(VERBOSE).unwrap();
+
+    assert_eq!(
+        header.executable("ffmpeg"),
+        Some(&Dependency {
+            name: "ffmpeg".to_string(),
+            version: "7.0.1".to_string(),
+            features: vec!["setts".to_string()],
+        })
+    );
+    assert_eq!(header.executable("ffprobe").unwrap().version, "7.0.1");
+    assert_eq!(header.executable("rtmpdump"), None);
+
+    assert_eq!(header.libraries.len(), 8);
+    assert_eq!(header.library("sqlite3").unwrap().version, "3.46.0");
+    assert_eq!(header.library("certifi").unwrap().version, "2024.07.04");
+}
+
+#[test]
+fn parses_minimal_header() {
+    let header = header(
+        "[debug] yt-dlp version 2023.03.04 [392389b7d] (zip)
+[debug] exe versions: none
+[debug] Optional libraries: none
+[debug] Proxy map: {}
+[debug] Plugin directories: none",
+    )
+    .unwrap();
+
+    assert_eq!(header.version, "2023.03.04");
+    assert_eq!(header.channel, None);
+    assert_eq!(header.origin, None);
+    assert_eq!(header.git_head.as_deref(), Some("392389b7d"));
+    assert_eq!(header.variant.as_deref(), Some("zip"));
+    assert!(header.executables.is_empty());
+    assert!(header.libraries.is_empty());
+    assert!(header.proxies.is_empty());
+    assert!(header.plugin_directories.is_empty());
+}
+
+#[test]
+fn requires_version() {
+    assert_eq!(
+        header("[debug] Encodings: locale UTF-8\n[youtube] Extracting URL: x"),
+        None
+    );
+}
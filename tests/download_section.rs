@@ -0,0 +1,122 @@
+use std::{str::FromStr, time::Duration};
+
+use yt_dlp::{Builder, DownloadSection, ParseErrorVariant, SectionTime};
+
+fn secs(secs: u64) -> Duration {
+    Duration::from_secs(secs)
+}
+
+#[test]
+fn renders_sections() {
+    assert_eq!(DownloadSection::from(secs(615)..).to_string(), "*615-inf");
+    assert_eq!(
+        DownloadSection::from(Duration::from_millis(1500)..secs(90)).to_string(),
+        "*1.5-90"
+    );
+    assert_eq!(DownloadSection::from(..secs(30)).to_string(), "*-30");
+    assert_eq!(
+        DownloadSection::time_range(Some(SectionTime::FromEnd(secs(60))), None).to_string(),
+        "*-60-inf"
+    );
+    assert_eq!(
+        DownloadSection::time_range(None, Some(SectionTime::FromEnd(secs(10)))).to_string(),
+        "*--10"
+    );
+    assert_eq!(
+        DownloadSection::chapters("(?i)intro").to_string(),
+        "(?i)intro"
+    );
+    assert_eq!(DownloadSection::FromUrl.to_string(), "*from-url");
+    assert_eq!(DownloadSection::chapters("*intro").to_string(), r"\*intro");
+}
+
+#[test]
+fn round_trips_escaped_chapters() {
+    let section = DownloadSection::chapters("*intro");
+    assert_eq!(
+        DownloadSection::from_str(&section.to_string()).unwrap(),
+        section
+    );
+}
+
+#[test]
+fn parses_sections() {
+    for (section, expected) in [
+        ("*10:15-inf", DownloadSection::from(secs(615)..)),
+        (
+            "*1:00:00-1:00:30.5",
+            DownloadSection::from(secs(3600)..Duration::from_secs_f64(3630.5)),
+        ),
+        ("*10-", DownloadSection::from(secs(10)..)),
+        ("*-30", DownloadSection::from(..secs(30))),
+        (
+            "*-60 - -10",
+            DownloadSection::time_range(
+                Some(SectionTime::FromEnd(secs(60))),
+                Some(SectionTime::FromEnd(secs(10))),
+            ),
+        ),
+        (
+            "*1h30m-1h30m15.5s",
+            DownloadSection::from(secs(5400)..Duration::from_secs_f64(5415.5)),
+        ),
+        ("*90s-2 mins", DownloadSection::from(secs(90)..secs(120))),
+        (
+            "*PT1H-P1DT2H",
+            DownloadSection::from(secs(3600)..secs(93600)),
+        ),
+        ("*1:00:00:00-inf", DownloadSection::from(secs(86400)..)),
+        ("*1.5hours-inf", DownloadSection::from(secs(5400)..)),
+        ("intro", DownloadSection::chapters("intro")),
+        (r"\*intro", DownloadSection::chapters("*intro")),
+        (r"\d+\*", DownloadSection::chapters(r"\d+\*")),
+        ("*from-url", DownloadSection::FromUrl),
+    ] {
+        assert_eq!(
+            DownloadSection::from_str(section).unwrap(),
+            expected,
+            "{section}"
+        );
+    }
+}
+
+#[test]
+fn rejects_invalid_time_ranges() {
+    for section in [
+        "*-",
+        "*10:15",
+        "*1:2:3:4:5-inf",
+        "*1:100-inf",
+        "*30m1h-inf",
+        "*1x-inf",
+        "*a-b",
+        "*0-10,20-30",
+    ] {
+        let error = DownloadSection::from_str(section).unwrap_err();
+        assert!(matches!(error.error(), ParseErrorVariant::InvalidTimeRange));
+        assert_eq!(error.content(), section);
+    }
+}
+
+#[test]
+fn download_sections_accepts_multiple_sections() {
+    let mut builder = Builder::new("yt-dlp");
+    builder
+        .download_sections(DownloadSection::from(secs(615)..))
+        .download_sections(DownloadSection::chapters("intro"));
+
+    let args = builder
+        .command_mut()
+        .get_args()
+        .map(|arg| arg.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        args,
+        [
+            "--download-sections",
+            "*615-inf",
+            "--download-sections",
+            "intro"
+        ]
+    );
+}
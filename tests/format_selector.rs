@@ -0,0 +1,153 @@
+use std::str::FromStr;
+
+use yt_dlp::{
+    Builder, Comparison, FormatFilter, FormatSelector, FormatSpec, ParseErrorVariant, Streams,
+};
+
+fn round_trip(s: &str) -> String {
+    FormatSelector::from_str(s).unwrap().to_string()
+}
+
+#[test]
+fn builds_selectors() {
+    let selector = FormatSelector::best_video()
+        .filter(FormatFilter::new("height", Comparison::Le, "720").none_inclusive())
+        .filter(FormatFilter::new("ext", Comparison::Eq, "mp4"))
+        .merge(FormatSelector::best_audio())
+        .or(FormatSelector::best());
+
+    assert_eq!(
+        selector.to_string(),
+        "bestvideo[height<=?720][ext=mp4]+bestaudio/best"
+    );
+}
+
+#[test]
+fn parenthesises_lower_precedence() {
+    let selector = FormatSelector::id("mp4")
+        .and(FormatSelector::id("webm"))
+        .filter(FormatFilter::new("height", Comparison::Lt, "480"))
+        .merge(FormatSelector::best_audio().or(FormatSelector::id("140")));
+
+    assert_eq!(
+        selector.to_string(),
+        "(mp4,webm)[height<480]+(bestaudio/140)"
+    );
+    assert_eq!(
+        FormatSelector::from_str(&selector.to_string()).unwrap(),
+        selector
+    );
+}
+
+#[test]
+fn parses_structure() {
+    let selector = FormatSelector::from_str("bv*+ba/b").unwrap();
+
+    assert_eq!(
+        selector,
+        FormatSelector::Fallback(vec![
+            FormatSelector::Merge(vec![
+                FormatSelector::new(FormatSpec::Best(Streams::Video, None)),
+                FormatSelector::new(FormatSpec::Best(Streams::AudioOnly, None)),
+            ]),
+            FormatSelector::new(FormatSpec::Best(Streams::Both, None)),
+        ])
+    );
+    assert_eq!(selector.to_string(), "bestvideo*+bestaudio/best");
+}
+
+#[test]
+fn round_trips() {
+    for s in [
+        "best",
+        "worst*.2",
+        "bestvideo.3+bestaudio",
+        "all",
+        "mergeall[vcodec=none]",
+        "137+140/22/18",
+        "hls-2.1",
+        "dash-video=123000+dash-audio=128000",
+        "hd/sd/source/webm",
+        "bestvideo[height<=?1080][fps>30][vcodec!^=avc1]+bestaudio[acodec~=^(opus|vorbis)$]",
+        "(mp4,webm)[height<480]",
+        "best[format_note*='HD Ready']",
+        "bestaudio[language!=en]",
+    ] {
+        assert_eq!(round_trip(s), s);
+    }
+
+    assert_eq!(round_trip("bv + ba / b"), "bestvideo+bestaudio/best");
+    assert_eq!(round_trip("(bv+ba)"), "bestvideo+bestaudio");
+    assert_eq!(round_trip("b[ height <= 720 ]"), "best[height<=720]");
+    assert_eq!(round_trip("b[ext='mp4']"), "best[ext=mp4]");
+}
+
+#[test]
+fn escapes_quotes_in_values() {
+    let filter = FormatFilter::new("format_note", Comparison::Contains, r#"it's "HD""#);
+    assert_eq!(filter.to_string(), r#"[format_note*='it\'s "HD"']"#);
+
+    let selector = FormatSelector::best().filter(filter);
+    assert_eq!(
+        FormatSelector::from_str(&selector.to_string()).unwrap(),
+        selector
+    );
+    assert_eq!(
+        FormatSelector::from_str(r#"b[format_note*="say \"hi\""]"#).unwrap(),
+        FormatSelector::best().filter(FormatFilter::new(
+            "format_note",
+            Comparison::Contains,
+            r#"say "hi""#
+        ))
+    );
+}
+
+#[test]
+fn rejects_malformed() {
+    for (s, position) in [
+        ("", 0),
+        ("bv+", 3),
+        ("bv+ba/", 6),
+        ("(bv+ba", 6),
+        ("bv[height]", 9),
+        ("bv[<720]", 3),
+        ("bv[height<=]", 11),
+        ("bv[height<=720", 14),
+        ("best.x", 5),
+        ("bestvideo**", 0),
+        ("bv)", 2),
+        ("bv[ext='mp4]", 12),
+        ("bestvideo+bestaudio/bets", 20),
+        ("bestvdeo", 0),
+        ("wrost.2", 0),
+        ("mp4,bestaudios", 4),
+    ] {
+        let error = FormatSelector::from_str(s).unwrap_err();
+        assert!(
+            matches!(
+                error.error(),
+                ParseErrorVariant::InvalidFormatSelector { position: p, .. } if *p == position
+            ),
+            "{s}: {error}"
+        );
+        assert_eq!(error.content(), s);
+    }
+}
+
+#[test]
+fn format_accepts_selectors() {
+    let mut builder = Builder::new("yt-dlp");
+    builder
+        .format(FormatSelector::best_video().merge(FormatSelector::best_audio()))
+        .format("bv*+ba/b");
+
+    let args = builder
+        .command_mut()
+        .get_args()
+        .map(|arg| arg.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        args,
+        ["--format", "bestvideo+bestaudio", "--format", "bv*+ba/b"]
+    );
+}
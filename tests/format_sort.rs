@@ -0,0 +1,73 @@
+use std::str::FromStr;
+
+use yt_dlp::{Builder, FormatSort, ParseErrorVariant, SortField, SortKey, SortPreference};
+
+#[test]
+fn builds_sort_order() {
+    let sort = FormatSort::new()
+        .then(SortKey::new(SortField::Resolution).limit(1080))
+        .then(SortKey::new(SortField::Size).reversed())
+        .then(SortKey::new(SortField::VideoCodec).limit("h264"))
+        .then(SortKey::new(SortField::Filesize).closest("1G"))
+        .then(SortField::Hdr);
+
+    assert_eq!(
+        sort.to_string(),
+        "res:1080,+size,vcodec:h264,filesize~1G,hdr"
+    );
+}
+
+#[test]
+fn parses_sort_order() {
+    let sort = FormatSort::from_str("res:1080, +size,vcodec:h264,fs_approx~500M").unwrap();
+
+    assert_eq!(
+        sort.keys(),
+        [
+            SortKey {
+                field: SortField::Resolution,
+                reverse: false,
+                preference: Some(SortPreference::Limit("1080".to_string())),
+            },
+            SortKey::new(SortField::Size).reversed(),
+            SortKey::new(SortField::VideoCodec).limit("h264"),
+            SortKey::new(SortField::FilesizeApprox).closest("500M"),
+        ]
+    );
+    assert_eq!(
+        sort.to_string(),
+        "res:1080,+size,vcodec:h264,fs_approx~500M"
+    );
+}
+
+#[test]
+fn rejects_unknown_fields() {
+    let error = FormatSort::from_str("res,+resolution").unwrap_err();
+    assert!(matches!(
+        error.error(),
+        ParseErrorVariant::UnknownSortField(field) if field == "resolution"
+    ));
+    assert_eq!(error.content(), "res,+resolution");
+
+    assert!(matches!(
+        FormatSort::from_str("res,").unwrap_err().error(),
+        ParseErrorVariant::UnknownSortField(field) if field.is_empty()
+    ));
+    assert!(matches!(
+        FormatSort::from_str("res:").unwrap_err().error(),
+        ParseErrorVariant::MissingSortValue(field) if field == "res"
+    ));
+}
+
+#[test]
+fn format_sort_accepts_typed_order() {
+    let mut builder = Builder::new("yt-dlp");
+    builder.format_sort(FormatSort::new().then(SortField::Ext).then(SortField::Fps));
+
+    let args = builder
+        .command_mut()
+        .get_args()
+        .map(|arg| arg.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(args, ["--format-sort", "ext,fps"]);
+}
@@ -0,0 +1,164 @@
+#[cfg(unix)]
+mod common;
+
+use std::str::FromStr;
+
+use yt_dlp::{Info, ParseErrorVariant, PlaylistInfo, VideoInfo};
+
+const VIDEO: &str = include_str!("fixtures/video_info.json");
+const PLAYLIST: &str = include_str!("fixtures/playlist_info.json");
+
+#[test]
+fn parses_video() {
+    let info = VideoInfo::from_str(VIDEO).unwrap();
+
+    assert_eq!(info.id, "aqz-KE-bpKQ");
+    assert_eq!(info.channel.as_deref(), Some("Blender"));
+    assert_eq!(info.duration, Some(635.));
+    assert_eq!(info.formats.len(), 5);
+    assert_eq!(info.thumbnails[1].width, Some(1920));
+    assert_eq!(info.subtitles["en"].len(), 2);
+    assert!(info.automatic_captions.is_empty());
+    assert_eq!(
+        info.chapters
+            .iter()
+            .map(|chapter| chapter.title.as_deref().unwrap())
+            .collect::<Vec<_>>(),
+        ["Intro", "The Bunny", "Credits"]
+    );
+    assert_eq!(
+        info.requested_downloads[0].filename.as_deref(),
+        Some("Big Buck Bunny 60fps 4K - Official Blender Foundation Short Film [aqz-KE-bpKQ].mkv")
+    );
+    assert_eq!(info.extra["live_status"], "not_live");
+    assert!(!info.extra.contains_key("title"));
+}
+
+#[test]
+fn parses_playlist() {
+    let info = PlaylistInfo::from_str(PLAYLIST).unwrap();
+
+    assert_eq!(info.title.as_deref(), Some("Blender Open Movies"));
+    assert_eq!(info.playlist_count, Some(3));
+    assert!(info.thumbnails.is_empty());
+    assert_eq!(
+        info.videos()
+            .map(|entry| entry.id.as_str())
+            .collect::<Vec<_>>(),
+        ["aqz-KE-bpKQ", "eRsGyueVLvQ", "R6MlUcmOul8"]
+    );
+}
+
+#[test]
+fn parses_incomplete_and_nested_playlists() {
+    let info = PlaylistInfo::from_str(
+        r#"{
+            "id": "channel",
+            "_type": "playlist",
+            "entries": [
+                null,
+                {"id": "videos", "_type": "playlist", "entries": [{"id": "a", "timestamp": 1700000000.5}]},
+                {"id": "b", "_type": "url", "timestamp": 1700000001}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(info.entries.len(), 3);
+    assert!(info.entries[0].is_none());
+    assert!(matches!(&info.entries[1], Some(Info::Playlist(playlist)) if playlist.id == "videos"));
+    assert_eq!(
+        info.videos()
+            .map(|video| (video.id.as_str(), video.timestamp))
+            .collect::<Vec<_>>(),
+        [("a", Some(1700000000)), ("b", Some(1700000001))]
+    );
+}
+
+#[test]
+fn info_dispatches_on_type() {
+    assert!(matches!(Info::from_str(VIDEO), Ok(Info::Video(_))));
+    assert!(matches!(Info::from_str(PLAYLIST), Ok(Info::Playlist(_))));
+    assert!(matches!(
+        Info::from_str(r#"{"title": "no id"}"#).unwrap_err().error(),
+        ParseErrorVariant::InvalidInfo(_)
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn fetch_info_parses_stdout() {
+    use yt_dlp::Builder;
+
+    let ytdlp = common::fake_ytdlp(
+        "fetch_info_parses_stdout",
+        &format!(
+            r#"case "$*" in *--dump-single-json*) ;; *) exit 2 ;; esac
+cat "{}/tests/fixtures/video_info.json""#,
+            env!("CARGO_MANIFEST_DIR")
+        ),
+    );
+
+    let mut builder = Builder::new(ytdlp.to_str().unwrap());
+    for _ in 0..2 {
+        let info = builder.fetch_info().unwrap();
+        assert!(matches!(info, Info::Video(video) if video.id == "aqz-KE-bpKQ"));
+    }
+    assert_eq!(
+        builder
+            .command_mut()
+            .get_args()
+            .filter(|arg| *arg == "--dump-single-json")
+            .count(),
+        1
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn fetch_info_reports_failure() {
+    use yt_dlp::{Builder, RunError};
+
+    let ytdlp = common::fake_ytdlp(
+        "fetch_info_reports_failure",
+        r#"echo "ERROR: [youtube] abc: Video unavailable" >&2
+exit 1"#,
+    );
+
+    let Err(RunError::Failed(execution)) = Builder::new(ytdlp.to_str().unwrap()).fetch_info()
+    else {
+        panic!("expected yt-dlp to fail");
+    };
+    assert_eq!(execution.errors().count(), 1);
+}
+
+#[test]
+fn typed_formats() {
+    let info = VideoInfo::from_str(VIDEO).unwrap();
+    let [storyboard, audio, video, muxed, hdr] = info.formats.as_slice() else {
+        panic!("expected five formats");
+    };
+
+    assert!(!storyboard.is_audio_only() && !storyboard.is_video_only());
+    assert_eq!(storyboard.resolution(), "48x27");
+
+    assert!(audio.is_audio_only());
+    assert_eq!(audio.resolution(), "audio only");
+    assert_eq!(audio.acodec.as_deref(), Some("opus"));
+    assert_eq!(audio.abr, Some(127.421));
+    assert_eq!(audio.language.as_deref(), Some("en"));
+    assert_eq!(audio.size(), Some(10135439));
+
+    assert!(video.is_video_only());
+    assert_eq!(video.resolution(), "1920x1080");
+    assert_eq!(video.fps, Some(60.));
+
+    assert!(!muxed.is_audio_only() && !muxed.is_video_only());
+    assert_eq!(muxed.filesize, None);
+    assert_eq!(muxed.size(), Some(37497021));
+
+    assert_eq!(hdr.protocol.as_deref(), Some("m3u8_native"));
+    assert_eq!(hdr.dynamic_range.as_deref(), Some("HDR10"));
+    assert_eq!(hdr.format_note.as_deref(), Some("2160p60 HDR"));
+    assert_eq!(hdr.extra["source_preference"], 99);
+}
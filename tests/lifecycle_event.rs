@@ -0,0 +1,101 @@
+use std::{path::Path, str::FromStr};
+
+use yt_dlp::{LifecycleEvent, Message, PostProcessorName};
+
+fn event(line: &str) -> Option<LifecycleEvent> {
+    LifecycleEvent::from_message(&Message::from_str(line).unwrap())
+}
+
+#[test]
+fn parses_extraction() {
+    assert_eq!(
+        event("[youtube] Extracting URL: https://www.youtube.com/watch?v=dQw4w9WgXcQ"),
+        Some(LifecycleEvent::Extracting {
+            extractor: "youtube".to_string(),
+            video_id: None,
+            step: "Extracting URL: https://www.youtube.com/watch?v=dQw4w9WgXcQ".to_string(),
+        })
+    );
+    assert_eq!(
+        event("[youtube] dQw4w9WgXcQ: Downloading webpage"),
+        Some(LifecycleEvent::Extracting {
+            extractor: "youtube".to_string(),
+            video_id: Some("dQw4w9WgXcQ".to_string()),
+            step: "Downloading webpage".to_string(),
+        })
+    );
+    assert_eq!(
+        event("[info] dQw4w9WgXcQ: Downloading 2 format(s): 137+140, 22"),
+        Some(LifecycleEvent::FormatsChosen {
+            video_id: "dQw4w9WgXcQ".to_string(),
+            formats: vec!["137+140".to_string(), "22".to_string()],
+        })
+    );
+}
+
+#[test]
+fn parses_download_paths() {
+    assert_eq!(
+        event("[download] Destination: Rick Astley [dQw4w9WgXcQ].f137.mp4"),
+        Some(LifecycleEvent::Destination(
+            "Rick Astley [dQw4w9WgXcQ].f137.mp4".into()
+        ))
+    );
+    assert_eq!(
+        event("[download] Rick Astley [dQw4w9WgXcQ].mkv has already been downloaded"),
+        Some(LifecycleEvent::AlreadyDownloaded(
+            "Rick Astley [dQw4w9WgXcQ].mkv".into()
+        ))
+    );
+    assert_eq!(
+        event("[Merger] Merging formats into \"Rick Astley [dQw4w9WgXcQ].mkv\"")
+            .unwrap()
+            .path(),
+        Some(Path::new("Rick Astley [dQw4w9WgXcQ].mkv"))
+    );
+    assert_eq!(
+        event("Deleting original file video.f137.mp4 (pass -k to keep)"),
+        Some(LifecycleEvent::DeletingOriginal("video.f137.mp4".into()))
+    );
+}
+
+#[test]
+fn parses_post_processing() {
+    assert_eq!(
+        event("[ExtractAudio] Destination: video.mp3"),
+        Some(LifecycleEvent::PostProcess {
+            post_processor: PostProcessorName::ExtractAudio,
+            destination: Some("video.mp3".into()),
+        })
+    );
+    assert_eq!(
+        event("[VideoConvertor] Converting video from webm to mp4; Destination: video.mp4"),
+        Some(LifecycleEvent::PostProcess {
+            post_processor: PostProcessorName::VideoConvertor,
+            destination: Some("video.mp4".into()),
+        })
+    );
+    assert_eq!(
+        event("[Metadata] Adding metadata to \"video.mp4\""),
+        Some(LifecycleEvent::PostProcess {
+            post_processor: PostProcessorName::Metadata,
+            destination: None,
+        })
+    );
+    assert_eq!(
+        event("[MoveFiles] Moving file \"tmp/video.mp4\" to \"videos/video.mp4\"")
+            .unwrap()
+            .path(),
+        Some(Path::new("videos/video.mp4"))
+    );
+}
+
+#[test]
+fn ignores_other_messages() {
+    assert_eq!(event("[youtube] dQw4w9WgXcQ: Some other message"), None);
+    assert_eq!(event("[download] Downloading playlist: Videos"), None);
+    assert_eq!(
+        event("WARNING: [youtube] dQw4w9WgXcQ: Downloading webpage"),
+        None
+    );
+}
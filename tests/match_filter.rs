@@ -0,0 +1,161 @@
+use std::str::FromStr;
+
+use yt_dlp::{Builder, Comparison, MatchCondition, MatchFilter, ParseErrorVariant, VideoInfo};
+
+const VIDEO: &str = include_str!("fixtures/video_info.json");
+
+fn video() -> VideoInfo {
+    VideoInfo::from_str(VIDEO).unwrap()
+}
+
+fn evaluate(filter: &str) -> bool {
+    MatchFilter::from_str(filter).unwrap().evaluate(&video())
+}
+
+#[test]
+fn builds_filters() {
+    let filter = MatchFilter::new()
+        .and(MatchCondition::absent("is_live"))
+        .and(MatchCondition::compare("like_count", Comparison::Gt, 100).none_inclusive())
+        .and(MatchCondition::compare(
+            "description",
+            Comparison::Matches,
+            r"(?i)\bcats & dogs\b",
+        ));
+
+    assert_eq!(
+        filter.to_string(),
+        r"!is_live & like_count>?100 & description~=(?i)\bcats \& dogs\b"
+    );
+}
+
+#[test]
+fn parses_filters() {
+    let filter =
+        MatchFilter::from_str(r"!is_live & like_count>?100 & description~='(?i)\bcats \& dogs\b'")
+            .unwrap();
+
+    assert_eq!(
+        filter.conditions(),
+        [
+            MatchCondition::Absent("is_live".to_string()),
+            MatchCondition::Compare {
+                key: "like_count".to_string(),
+                comparison: Comparison::Gt,
+                value: "100".to_string(),
+                none_inclusive: true,
+            },
+            MatchCondition::compare("description", Comparison::Matches, r"(?i)\bcats & dogs\b"),
+        ]
+    );
+
+    assert_eq!(
+        MatchFilter::from_str("duration !< 60")
+            .unwrap()
+            .conditions(),
+        [MatchCondition::compare("duration", Comparison::Ge, 60)]
+    );
+    assert_eq!(
+        MatchFilter::from_str("title!*=trailer")
+            .unwrap()
+            .conditions(),
+        [MatchCondition::compare(
+            "title",
+            Comparison::NotContains,
+            "trailer"
+        )]
+    );
+}
+
+#[test]
+fn round_trips_quotes() {
+    let filter = MatchFilter::new()
+        .and(MatchCondition::compare(
+            "title",
+            Comparison::StartsWith,
+            "'quoted'",
+        ))
+        .and(MatchCondition::compare(
+            "uploader",
+            Comparison::Eq,
+            " padded ",
+        ));
+
+    assert_eq!(
+        filter.to_string(),
+        r"title^='\'quoted\'' & uploader=' padded '"
+    );
+    assert_eq!(MatchFilter::from_str(&filter.to_string()).unwrap(), filter);
+}
+
+#[test]
+fn rejects_malformed_conditions() {
+    let error = MatchFilter::from_str("is_live & like_count%100").unwrap_err();
+    assert!(matches!(
+        error.error(),
+        ParseErrorVariant::InvalidMatchCondition(condition) if condition == "like_count%100"
+    ));
+    assert_eq!(error.content(), "is_live & like_count%100");
+
+    assert!(MatchFilter::from_str("is_live &").is_err());
+    assert!(MatchFilter::from_str("title=").is_err());
+    assert!(MatchFilter::from_str("Title=x").is_err());
+}
+
+#[test]
+fn evaluates_presence() {
+    assert!(evaluate("!is_live"));
+    assert!(evaluate("playable_in_embed"));
+    assert!(!evaluate("is_live"));
+    assert!(evaluate("!average_rating & !missing_field"));
+    assert!(!evaluate("average_rating"));
+}
+
+#[test]
+fn evaluates_numbers() {
+    assert!(evaluate("like_count>100"));
+    assert!(evaluate("duration<11m & duration>=10:00"));
+    assert!(evaluate("filesize_approx<100M & filesize_approx>69MiB"));
+    assert!(!evaluate("view_count<1k"));
+    assert!(!evaluate("title<10"));
+    assert!(!evaluate("like_count*=184"));
+
+    assert!(!evaluate("average_rating>3"));
+    assert!(evaluate("average_rating>?3"));
+}
+
+#[test]
+fn evaluates_strings() {
+    assert!(evaluate(
+        "title^=Big & title$=Film & title*='Blender Foundation'"
+    ));
+    assert!(evaluate("live_status=not_live & channel!=Other"));
+    assert!(evaluate(r"description~='(?i)GIANT\s+rabbit'"));
+    assert!(!evaluate("description!~=rabbit"));
+    assert!(evaluate("tags*=blender & tags!*=cats"));
+}
+
+#[test]
+fn match_filters_accepts_typed_filter() {
+    let filter = MatchFilter::new().and(MatchCondition::absent("is_live"));
+
+    let mut builder = Builder::new("yt-dlp");
+    builder
+        .match_filters(&filter)
+        .break_match_filters("duration<60");
+
+    let args = builder
+        .command_mut()
+        .get_args()
+        .map(|arg| arg.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        args,
+        [
+            "--match-filters",
+            "!is_live",
+            "--break-match-filters",
+            "duration<60"
+        ]
+    );
+}
@@ -0,0 +1,91 @@
+use std::str::FromStr;
+
+use yt_dlp::{Message, MessageType, Output, ParseErrorVariant};
+
+const CORPUS: &str = include_str!("fixtures/messages.tsv");
+
+fn message_type(s: &str) -> MessageType {
+    match s {
+        "Warning" => MessageType::Warning,
+        "Error" => MessageType::Error,
+        "Debug" => MessageType::Debug,
+        "Neutral" => MessageType::Neutral,
+        _ => panic!("unknown message type `{s}` in corpus"),
+    }
+}
+
+#[test]
+fn parses_corpus() {
+    for row in CORPUS.lines().filter(|row| !row.starts_with('#')) {
+        let [line, r#type, tag, content] = row.split('\t').collect::<Vec<_>>()[..] else {
+            panic!("malformed corpus row `{row}`");
+        };
+
+        let message = Message::from_str(line).unwrap();
+        assert_eq!(message.message_type(), message_type(r#type), "{line}");
+        assert_eq!(message.tag(), tag, "{line}");
+        assert_eq!(message.content(), content, "{line}");
+        assert_eq!(message.line(), line);
+        assert_eq!(message.to_string(), line);
+    }
+}
+
+#[test]
+fn strips_ansi_codes() {
+    let line = "\x1b[0;31mERROR:\x1b[0m [youtube] dQw4w9WgXcQ: Private video";
+    let message = Message::from_str(line).unwrap();
+
+    assert_eq!(message.message_type(), MessageType::Error);
+    assert_eq!(message.tag(), "youtube");
+    assert_eq!(message.content(), "dQw4w9WgXcQ: Private video");
+    assert_eq!(message.line(), line);
+    assert_eq!(
+        message.to_string(),
+        "ERROR: [youtube] dQw4w9WgXcQ: Private video"
+    );
+}
+
+#[test]
+fn keeps_last_redraw() {
+    let line = "\r[download]  10.0% of 40.00MiB\r\x1b[K[download]  50.0% of 40.00MiB\r";
+    let message = Message::from_str(line).unwrap();
+
+    assert_eq!(message.tag(), "download");
+    assert_eq!(message.content(), "50.0% of 40.00MiB");
+    assert_eq!(message.line(), line);
+}
+
+#[test]
+fn rejects_unknown_types() {
+    let error = MessageType::from_str("DONNOWHA").unwrap_err();
+    assert_eq!(error.content(), "DONNOWHA");
+    assert!(matches!(
+        error.error(),
+        ParseErrorVariant::UnknownMessageType(r#type) if r#type == "DONNOWHA"
+    ));
+}
+
+#[test]
+fn constructed_messages_render_line() {
+    let message = Message::new(
+        MessageType::Warning,
+        "youtube".to_string(),
+        "dQw4w9WgXcQ: nsig extraction failed".to_string(),
+    );
+    assert_eq!(
+        message.line(),
+        "WARNING: [youtube] dQw4w9WgXcQ: nsig extraction failed"
+    );
+    assert_eq!(Message::from_str(message.line()).unwrap(), message);
+
+    let message = Message::new(MessageType::Debug, "debug".to_string(), "x".to_string());
+    assert_eq!(message.line(), "[debug] x");
+}
+
+#[test]
+fn output_strips_ansi_codes() {
+    assert!(matches!(
+        Output::from_str("\x1b[0;33mWARNING:\x1b[0m ffmpeg not found"),
+        Ok(Output::Message(message)) if message.message_type() == MessageType::Warning
+    ));
+}
@@ -0,0 +1,69 @@
+use std::str::FromStr;
+
+use yt_dlp::{Builder, OutputLayout, OutputTemplate, OutputType};
+
+fn template(s: &str) -> OutputTemplate {
+    OutputTemplate::from_str(s).unwrap()
+}
+
+#[test]
+fn applies_templates_and_paths() {
+    let layout = OutputLayout::new()
+        .template(template("%(title)s [%(id)s].%(ext)s"))
+        .template_for(OutputType::Thumbnail, template("thumbs/%(id)s.%(ext)s"))
+        .template_for(
+            OutputType::PlaylistVideo,
+            template("%(playlist)s/%(title)s.%(ext)s"),
+        )
+        .template_for(OutputType::Subtitle, template("subs/%(id)s.%(ext)s"))
+        .home("/srv/media")
+        .temp("/tmp/yt-dlp")
+        .path_for(OutputType::InfoJson, "/srv/metadata");
+
+    let mut builder = Builder::new("yt-dlp");
+    builder.output_layout(&layout);
+
+    let args = builder
+        .command_mut()
+        .get_args()
+        .map(|arg| arg.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        args,
+        [
+            "--output",
+            "%(title)s [%(id)s].%(ext)s",
+            "--output",
+            "subtitle:subs/%(id)s.%(ext)s",
+            "--output",
+            "thumbnail:thumbs/%(id)s.%(ext)s",
+            "--output",
+            "pl_video:%(playlist)s/%(title)s.%(ext)s",
+            "--paths",
+            "home:/srv/media",
+            "--paths",
+            "temp:/tmp/yt-dlp",
+            "--paths",
+            "infojson:/srv/metadata",
+        ]
+    );
+}
+
+#[test]
+fn later_settings_replace_earlier_ones() {
+    let layout = OutputLayout::new()
+        .template_for(OutputType::Chapter, template("a.%(ext)s"))
+        .template_for(OutputType::Chapter, template("b.%(ext)s"))
+        .home("/a")
+        .home("/b");
+
+    assert_eq!(layout.output_args(), ["chapter:b.%(ext)s"]);
+    assert_eq!(layout.paths_args(), ["home:/b"]);
+}
+
+#[test]
+fn empty_layout_adds_nothing() {
+    let mut builder = Builder::new("yt-dlp");
+    builder.output_layout(&OutputLayout::new());
+    assert_eq!(builder.command_mut().get_args().count(), 0);
+}
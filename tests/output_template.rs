@@ -0,0 +1,172 @@
+use std::str::FromStr;
+
+use yt_dlp::{
+    Builder, FieldExpression, MathOp, OutputTemplate, ParseErrorVariant, TemplateField,
+    TemplatePart, VideoInfo,
+};
+
+const VIDEO: &str = include_str!("fixtures/video_info.json");
+
+fn video() -> VideoInfo {
+    VideoInfo::from_str(VIDEO).unwrap()
+}
+
+fn render(template: &str) -> String {
+    OutputTemplate::from_str(template).unwrap().render(&video())
+}
+
+#[test]
+fn builds_templates() {
+    let template = OutputTemplate::new()
+        .field(
+            TemplateField::new("uploader")
+                .or("channel")
+                .default("Unknown"),
+        )
+        .literal("/")
+        .field(TemplateField::new("title").precision(50))
+        .literal(" [")
+        .field(TemplateField::new("id"))
+        .literal("].")
+        .field(TemplateField::new("ext"));
+
+    assert_eq!(
+        template.to_string(),
+        "%(uploader,channel|Unknown)s/%(title).50s [%(id)s].%(ext)s"
+    );
+    assert_eq!(
+        template.render(&video()),
+        "Blender/Big Buck Bunny 60fps 4K - Official Blender Foundat [aqz-KE-bpKQ].mkv"
+    );
+}
+
+#[test]
+fn parses_fields() {
+    let template = OutputTemplate::from_str(
+        "%(release_date>%Y-%m-%d,upload_date|Unknown)s - %(playlist_index-1)03d%%",
+    )
+    .unwrap();
+
+    let [TemplatePart::Field(date), TemplatePart::Literal(separator), TemplatePart::Field(index), TemplatePart::Literal(percent)] =
+        template.parts()
+    else {
+        panic!("unexpected parts {:?}", template.parts());
+    };
+
+    assert_eq!(
+        date.alternatives,
+        [
+            FieldExpression {
+                path: "release_date".to_string(),
+                maths: Vec::new(),
+                date_format: Some("%Y-%m-%d".to_string()),
+            },
+            FieldExpression::new("upload_date"),
+        ]
+    );
+    assert_eq!(date.default.as_deref(), Some("Unknown"));
+    assert_eq!(separator, " - ");
+    assert_eq!(
+        index.alternatives[0].maths,
+        [(MathOp::Sub, "1".to_string())]
+    );
+    assert_eq!(
+        (index.flags.as_str(), index.width, index.conversion),
+        ("0", Some(3), 'd')
+    );
+    assert_eq!(percent, "%");
+}
+
+#[test]
+fn round_trips() {
+    for s in [
+        "%(title)s [%(id)s].%(ext)s",
+        "%(uploader)s/%(title).50s [%(id)s].%(ext)s",
+        "%(release_date>%Y-%m-%d,upload_date>%Y|Unknown)s",
+        "%(autonumber)05d - %(title)-20.10s",
+        "%(formats.-1.format_id)s",
+        "%(duration*1000+5)d",
+        "%(chapters&has chapters|no chapters)s",
+        "%(tags)#l 100%%",
+        "%(view_count)+d",
+    ] {
+        let template = OutputTemplate::from_str(s).unwrap();
+        assert_eq!(template.to_string(), s);
+    }
+}
+
+#[test]
+fn renders_conversions() {
+    assert_eq!(render("%(upload_date>%Y-%m-%d)s"), "2014-01-10");
+    assert_eq!(
+        render("%(timestamp>%Y-%m-%d %H-%M-%S)s"),
+        "2014-01-10 18-40-00"
+    );
+    assert_eq!(render("%(release_date,upload_date)s"), "20140110");
+    assert_eq!(render("%(release_date)s"), "NA");
+    assert_eq!(render("%(release_date|unreleased)s"), "unreleased");
+    assert_eq!(render("%(duration)05d"), "00635");
+    assert_eq!(render("%(duration-35)d"), "600");
+    assert_eq!(render("%(duration*2)s"), "1270");
+    assert_eq!(render("%(aspect_ratio).1f"), "1.8");
+    assert_eq!(render("%(categories)l"), "Film & Animation");
+    assert_eq!(render("%(tags)l"), "big buck bunny, blender");
+    assert_eq!(render("%(formats.-1.format_id)s"), "625");
+    assert_eq!(render("%(chapters.1.title)s"), "The Bunny");
+    assert_eq!(render("%(chapters&has chapters|none)s"), "has chapters");
+    assert_eq!(render("%(is_live)s"), "False");
+    assert_eq!(render("%(id)-15s|"), "aqz-KE-bpKQ    |");
+}
+
+#[test]
+fn sanitizes_values() {
+    assert_eq!(
+        render("%(title)s"),
+        "Big Buck Bunny 60fps 4K - Official Blender Foundation Short Film"
+    );
+    assert_eq!(render("%(upload_date>%Y/%m)s"), "2014\u{29F8}01");
+    assert_eq!(render("%(categories)j"), "[＂Film & Animation＂]");
+    assert_eq!(
+        render("%(title&{}: the movie)s"),
+        "Big Buck Bunny 60fps 4K - Official Blender Foundation Short Film： the movie"
+    );
+}
+
+#[test]
+fn rejects_malformed() {
+    for (s, position) in [
+        ("%(title", 7),
+        ("%(title)", 8),
+        ("%(title)z", 8),
+        ("%(title).s", 9),
+        ("%(duration+)d", 11),
+        ("%(upload_date>)s", 14),
+    ] {
+        let error = OutputTemplate::from_str(s).unwrap_err();
+        assert!(
+            matches!(
+                error.error(),
+                ParseErrorVariant::InvalidOutputTemplate { position: p, .. } if *p == position
+            ),
+            "{s}: {error}"
+        );
+    }
+}
+
+#[test]
+fn output_accepts_templates() {
+    let mut builder = Builder::new("yt-dlp");
+    builder.output(
+        OutputTemplate::new()
+            .field(TemplateField::new("id"))
+            .literal(".")
+            .field(TemplateField::new("ext")),
+    );
+
+    let args = builder
+        .command_mut()
+        .get_args()
+        .map(|arg| arg.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(args, ["--output", "%(id)s.%(ext)s"]);
+}
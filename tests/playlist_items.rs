@@ -0,0 +1,96 @@
+use std::str::FromStr;
+
+use yt_dlp::{Builder, ParseErrorVariant, PlaylistItem, PlaylistItems};
+
+fn indices(spec: &str, len: usize) -> Vec<usize> {
+    PlaylistItems::from_str(spec)
+        .unwrap()
+        .indices(len)
+        .collect()
+}
+
+#[test]
+fn builds_items() {
+    let items = PlaylistItems::new()
+        .then(1..=3)
+        .then(7)
+        .then(PlaylistItem::range(Some(-5), None).step(2))
+        .then(..=-2)
+        .then(PlaylistItem::from(..).step(-1));
+
+    assert_eq!(items.to_string(), "1:3,7,-5::2,:-2,::-1");
+}
+
+#[test]
+fn parses_items() {
+    let items = PlaylistItems::from_str("1:3,7,-5::2,4-6,+2:inf,::-1").unwrap();
+
+    assert_eq!(
+        items.items(),
+        [
+            PlaylistItem::Range {
+                start: Some(1),
+                end: Some(3),
+                step: 1,
+            },
+            PlaylistItem::Index(7),
+            PlaylistItem::range(Some(-5), None).step(2),
+            PlaylistItem::from(4..=6),
+            PlaylistItem::from(2..),
+            PlaylistItem::from(..).step(-1),
+        ]
+    );
+    assert_eq!(items.to_string(), "1:3,7,-5::2,4:6,2:,::-1");
+}
+
+#[test]
+fn rejects_invalid_items() {
+    for (spec, item) in [
+        ("1,,3", ""),
+        ("1::0", "1::0"),
+        ("1,1:3:", "1:3:"),
+        ("a", "a"),
+        ("1:2:3:4", "1:2:3:4"),
+    ] {
+        let error = PlaylistItems::from_str(spec).unwrap_err();
+        assert!(
+            matches!(error.error(), ParseErrorVariant::InvalidPlaylistItem(found) if found == item),
+            "{spec}"
+        );
+        assert_eq!(error.content(), spec);
+    }
+}
+
+#[test]
+fn selects_indices() {
+    assert_eq!(indices("1:3,7,-5::2", 15), [1, 2, 3, 7, 11, 13, 15]);
+    assert_eq!(indices("::-1", 4), [4, 3, 2, 1]);
+    assert_eq!(indices("-2:", 4), [3, 4]);
+    assert_eq!(indices(":-2", 4), [1, 2, 3]);
+    assert_eq!(indices("10:1:-3", 5), [4, 1]);
+    assert_eq!(indices("-10::3", 5), [2, 5]);
+    assert_eq!(indices("0,6,-6,2", 5), [2]);
+    assert_eq!(indices("2:,1", 3), [2, 3, 1]);
+    assert_eq!(indices(":", 0), Vec::<usize>::new());
+    assert_eq!(indices("1:3,2", 5), [1, 2, 3]);
+    assert_eq!(indices("3,1:4,::-1", 5), [3, 1, 2, 4, 5]);
+}
+
+#[test]
+#[should_panic]
+fn rejects_zero_step() {
+    PlaylistItem::from(1..=3).step(0);
+}
+
+#[test]
+fn playlist_items_accepts_typed_items() {
+    let mut builder = Builder::new("yt-dlp");
+    builder.playlist_items(PlaylistItems::new().then(1..=3).then(-1));
+
+    let args = builder
+        .command_mut()
+        .get_args()
+        .map(|arg| arg.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(args, ["--playlist-items", "1:3,-1"]);
+}
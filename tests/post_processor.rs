@@ -0,0 +1,149 @@
+use std::str::FromStr;
+
+use yt_dlp::{
+    ArgPosition, Builder, Executable, ParseErrorVariant, PostProcessor, PostProcessorArgs,
+    PostProcessorName, PostProcessorStage,
+};
+
+#[test]
+fn builds_post_processors() {
+    let post_processor = PostProcessor::new("NoopPP")
+        .when(PostProcessorStage::AfterMove)
+        .arg("verbose", true)
+        .arg("path", "/tmp/out");
+
+    assert_eq!(
+        post_processor.to_string(),
+        "NoopPP:when=after_move;verbose=true;path=/tmp/out"
+    );
+    assert_eq!(PostProcessor::new("NoopPP").to_string(), "NoopPP");
+}
+
+#[test]
+fn parses_post_processors() {
+    let post_processor = PostProcessor::from_str("NoopPP:when=before_dl;key=a=b").unwrap();
+    assert_eq!(
+        post_processor,
+        PostProcessor {
+            name: "NoopPP".to_string(),
+            when: Some(PostProcessorStage::BeforeDownload),
+            args: vec![("key".to_string(), "a=b".to_string())],
+        }
+    );
+    assert_eq!(
+        PostProcessor::from_str("NoopPP:").unwrap(),
+        PostProcessor::new("NoopPP")
+    );
+
+    let error = PostProcessor::from_str("NoopPP:when=later").unwrap_err();
+    assert!(matches!(
+        error.error(),
+        ParseErrorVariant::UnknownPostProcessorStage(stage) if stage == "later"
+    ));
+    assert_eq!(error.content(), "NoopPP:when=later");
+    assert!(matches!(
+        PostProcessor::from_str("NoopPP:verbose").unwrap_err().error(),
+        ParseErrorVariant::InvalidPostProcessorArg(arg) if arg == "verbose"
+    ));
+}
+
+#[test]
+fn quotes_post_processor_args() {
+    let args = PostProcessorArgs::new()
+        .post_processor(PostProcessorName::Merger)
+        .executable(Executable::ffmpeg().input(Some(1)))
+        .args(["-v", "quiet"]);
+    assert_eq!(args.to_string(), "Merger+ffmpeg_i1:-v quiet");
+
+    let args = PostProcessorArgs::new()
+        .executable(Executable::ffmpeg())
+        .arg("-metadata")
+        .arg("title=It's a \"test\"")
+        .arg("")
+        .arg("-vf")
+        .arg("scale=1280:-2,fps=30");
+    assert_eq!(
+        args.to_string(),
+        r#"ffmpeg:-metadata 'title=It'"'"'s a "test"' '' -vf scale=1280:-2,fps=30"#
+    );
+    assert_eq!(
+        PostProcessorArgs::from_str(&args.to_string()).unwrap(),
+        args
+    );
+
+    assert_eq!(
+        PostProcessorArgs::new().arg("key:value").to_string(),
+        "default:key:value"
+    );
+}
+
+#[test]
+fn parses_post_processor_args() {
+    assert_eq!(
+        PostProcessorArgs::from_str(r#"ExtractAudio+FFprobe_o:-show_entries "a b\"c" x\ y"#)
+            .unwrap(),
+        PostProcessorArgs {
+            post_processor: Some(PostProcessorName::ExtractAudio),
+            executable: Some(Executable::FFprobe(Some(ArgPosition::Output(None)))),
+            args: vec![
+                "-show_entries".to_string(),
+                "a b\"c".to_string(),
+                "x y".to_string()
+            ],
+        }
+    );
+    assert_eq!(
+        PostProcessorArgs::from_str("sponsorblock:-v").unwrap(),
+        PostProcessorArgs::new()
+            .post_processor(PostProcessorName::Other("sponsorblock".to_string()))
+            .arg("-v")
+    );
+    assert_eq!(
+        PostProcessorArgs::from_str("atomicparsley:--foo").unwrap(),
+        PostProcessorArgs::new()
+            .executable(Executable::AtomicParsley)
+            .arg("--foo")
+    );
+    assert_eq!(
+        PostProcessorArgs::from_str("-ss 10").unwrap(),
+        PostProcessorArgs::new().args(["-ss", "10"])
+    );
+
+    assert!(matches!(
+        PostProcessorArgs::from_str("Merger+ffmpeg_x:-v").unwrap_err().error(),
+        ParseErrorVariant::UnknownExecutable(executable) if executable == "ffmpeg_x"
+    ));
+    assert!(matches!(
+        PostProcessorArgs::from_str("ffmpeg:'-v")
+            .unwrap_err()
+            .error(),
+        ParseErrorVariant::UnterminatedQuote
+    ));
+}
+
+#[test]
+fn post_processor_options_accept_typed_arguments() {
+    let mut builder = Builder::new("yt-dlp");
+    builder
+        .use_postprocessor(PostProcessor::new("NoopPP").when(PostProcessorStage::Playlist))
+        .postprocessor_args(
+            PostProcessorArgs::new()
+                .post_processor(PostProcessorName::Merger)
+                .args(["-v", "quiet"]),
+        );
+
+    let args = builder
+        .command_mut()
+        .get_args()
+        .map(|arg| arg.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        args,
+        [
+            "--use-postprocessor",
+            "NoopPP:when=playlist",
+            "--postprocessor-args",
+            "Merger:-v quiet"
+        ]
+    );
+}
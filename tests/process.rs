@@ -0,0 +1,248 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::{os::unix::process::ExitStatusExt, time::Duration};
+
+use yt_dlp::{Builder, MessageType, Output, PROGRESS_PREFIX};
+
+#[test]
+fn run_collects_output() {
+    let ytdlp = common::fake_ytdlp(
+        "run_collects_output",
+        r#"echo "[youtube] abc: Downloading webpage"
+echo "WARNING: [youtube] abc: nsig extraction failed" >&2
+echo "ERROR: [youtube] abc: Video unavailable" >&2
+exit 1"#,
+    );
+
+    let execution = Builder::new(ytdlp.to_str().unwrap()).run().unwrap();
+
+    assert!(!execution.success());
+    assert_eq!(execution.status().code(), Some(1));
+    assert_eq!(execution.output().len(), 3);
+    assert!(execution.unparsed().is_empty());
+
+    let types = execution
+        .messages()
+        .map(|message| message.message_type())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        types,
+        [
+            MessageType::Neutral,
+            MessageType::Warning,
+            MessageType::Error
+        ]
+    );
+    assert_eq!(execution.errors().count(), 1);
+}
+
+#[test]
+fn run_passes_arguments() {
+    let ytdlp = common::fake_ytdlp("run_passes_arguments", r#"echo "[args] $*""#);
+
+    let execution = Builder::new(ytdlp.to_str().unwrap())
+        .simulate()
+        .retries(3)
+        .run()
+        .unwrap();
+
+    assert!(execution.success());
+    assert!(matches!(
+        execution.output(),
+        [Output::Message(message)] if message.message_type() == MessageType::Neutral
+    ));
+}
+
+#[test]
+fn unparsable_lines_are_kept() {
+    let ytdlp = common::fake_ytdlp(
+        "unparsable_lines_are_kept",
+        &format!(r#"echo '{PROGRESS_PREFIX}{{"id": "abc"}}'"#),
+    );
+
+    let execution = Builder::new(ytdlp.to_str().unwrap()).run().unwrap();
+
+    assert!(execution.output().is_empty());
+    assert_eq!(execution.unparsed().len(), 1);
+    assert_eq!(
+        execution.unparsed()[0].content(),
+        format!(r#"{PROGRESS_PREFIX}{{"id": "abc"}}"#)
+    );
+}
+
+#[test]
+fn unknown_message_types_are_neutral() {
+    let ytdlp = common::fake_ytdlp(
+        "unknown_message_types_are_neutral",
+        r#"echo "DONNOWHA: [youtube] abc""#,
+    );
+
+    let execution = Builder::new(ytdlp.to_str().unwrap()).run().unwrap();
+
+    assert!(execution.unparsed().is_empty());
+    assert!(matches!(
+        execution.output(),
+        [Output::Message(message)] if message.message_type() == MessageType::Neutral
+            && message.tag().is_empty()
+    ));
+}
+
+#[test]
+fn spawn_missing_binary_fails() {
+    assert!(Builder::new("/nonexistent/yt-dlp").spawn().is_err());
+}
+
+#[test]
+fn spawn_streams_lines_in_order() {
+    let ytdlp = common::fake_ytdlp(
+        "spawn_streams_lines_in_order",
+        r#"echo "[youtube] abc: Downloading webpage"
+sleep 0.1
+echo "WARNING: [youtube] abc: nsig extraction failed" >&2
+sleep 0.1
+printf "[download]   1.0%%\r[download]  50.0%%\r[download] 100.0%%\n""#,
+    );
+
+    let mut handle = Builder::new(ytdlp.to_str().unwrap()).spawn().unwrap();
+    let types = handle
+        .by_ref()
+        .map(|line| match line.unwrap() {
+            Output::Message(message) => message.message_type(),
+            Output::Progress(_) => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        types,
+        [
+            MessageType::Neutral,
+            MessageType::Warning,
+            MessageType::Neutral,
+            MessageType::Neutral,
+            MessageType::Neutral,
+        ]
+    );
+    assert!(handle.wait().unwrap().output().is_empty());
+}
+
+#[test]
+fn structured_progress_is_parsed() {
+    let ytdlp = common::fake_ytdlp(
+        "structured_progress_is_parsed",
+        r#"case "$*" in
+    *"--progress-template download:yt-dlp-rs:progress:%(progress)j"*) ;;
+    *) exit 2 ;;
+esac
+echo "[download] Destination: x.mp4"
+echo 'yt-dlp-rs:progress:{"status": "finished", "filename": "x.mp4", "total_bytes": 1, "speed": null, "_speed_str": "", "_total_bytes_str": "", "_elapsed_str": "", "_percent_str": "100.0%", "_default_template": ""}'"#,
+    );
+
+    let execution = Builder::new(ytdlp.to_str().unwrap())
+        .structured_progress()
+        .run()
+        .unwrap();
+
+    assert!(execution.success());
+    assert!(matches!(
+        execution.output(),
+        [Output::Message(_), Output::Progress(_)]
+    ));
+}
+
+const LOOPING: &str = r#"echo "[youtube] abc: Downloading webpage"
+while true; do sleep 0.05; done"#;
+
+#[test]
+fn cancel_interrupts_gracefully() {
+    let ytdlp = common::fake_ytdlp(
+        "cancel_interrupts_gracefully",
+        &format!(
+            r#"trap 'echo "ERROR: Interrupted by user" >&2; exit 1' INT
+{LOOPING}"#
+        ),
+    );
+
+    let mut handle = Builder::new(ytdlp.to_str().unwrap()).spawn().unwrap();
+    assert!(handle.next().is_some());
+
+    let execution = handle.cancel(Duration::from_secs(5)).unwrap();
+    assert_eq!(execution.status().code(), Some(1));
+    assert_eq!(
+        execution.errors().next().unwrap().content(),
+        "Interrupted by user"
+    );
+}
+
+#[test]
+fn cancel_kills_after_timeout() {
+    let ytdlp = common::fake_ytdlp(
+        "cancel_kills_after_timeout",
+        &format!("trap '' INT\n{LOOPING}"),
+    );
+
+    let mut handle = Builder::new(ytdlp.to_str().unwrap()).spawn().unwrap();
+    assert!(handle.next().is_some());
+
+    let execution = handle.cancel(Duration::from_millis(200)).unwrap();
+    assert_eq!(execution.status().signal(), Some(libc::SIGKILL));
+}
+
+#[test]
+fn cancel_kills_after_pipes_are_closed() {
+    let ytdlp = common::fake_ytdlp(
+        "cancel_kills_after_pipes_are_closed",
+        &format!("trap '' INT\nexec >&- 2>&-\n{LOOPING}"),
+    );
+
+    let handle = Builder::new(ytdlp.to_str().unwrap()).spawn().unwrap();
+    // there is no output to wait for, give the script time to set up the trap
+    std::thread::sleep(Duration::from_millis(100));
+
+    let execution = handle.cancel(Duration::from_millis(200)).unwrap();
+    assert_eq!(execution.status().signal(), Some(libc::SIGKILL));
+}
+
+#[test]
+fn drop_kills_the_process() {
+    let ytdlp = common::fake_ytdlp("drop_kills_the_process", LOOPING);
+
+    let mut handle = Builder::new(ytdlp.to_str().unwrap()).spawn().unwrap();
+    assert!(handle.next().is_some());
+    let pid = handle.id();
+    drop(handle);
+
+    // signal 0 only checks that the process exists, which includes zombies
+    assert_eq!(unsafe { libc::kill(pid as libc::pid_t, 0) }, -1);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn pause_and_resume() {
+    let ytdlp = common::fake_ytdlp("pause_and_resume", LOOPING);
+
+    let mut handle = Builder::new(ytdlp.to_str().unwrap()).spawn().unwrap();
+    assert!(handle.next().is_some());
+    let state = |pid: u32| {
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).unwrap();
+        stat.rsplit(')')
+            .next()
+            .unwrap()
+            .split_whitespace()
+            .next()
+            .unwrap()
+            .to_string()
+    };
+
+    handle.pause().unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(state(handle.id()), "T");
+
+    handle.resume().unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+    assert_ne!(state(handle.id()), "T");
+
+    handle.kill().unwrap();
+    assert!(handle.wait().is_ok());
+}
@@ -0,0 +1,156 @@
+use std::str::FromStr;
+
+use yt_dlp::{Builder, Output, ParseErrorVariant, Progress, ProgressStatus, PROGRESS_PREFIX};
+
+const PROGRESS: &str = include_str!("fixtures/progress.jsonl");
+const INVALID: &str = include_str!("fixtures/progress_invalid.txt");
+
+fn fixtures() -> Vec<Progress> {
+    PROGRESS
+        .lines()
+        .map(|line| Progress::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn parses_all_fixtures() {
+    let statuses = fixtures().iter().map(Progress::status).collect::<Vec<_>>();
+
+    assert_eq!(
+        statuses,
+        [
+            ProgressStatus::Downloading,
+            ProgressStatus::Downloading,
+            ProgressStatus::Finished,
+            ProgressStatus::Downloading,
+            ProgressStatus::Downloading,
+            ProgressStatus::Finished,
+        ]
+    );
+}
+
+#[test]
+fn downloading_fields() {
+    let Progress::Downloading {
+        downloaded_bytes,
+        total_bytes,
+        tempfilename,
+        eta,
+        percent_str,
+        ..
+    } = &fixtures()[1]
+    else {
+        panic!("expected a downloading line");
+    };
+
+    assert_eq!(*downloaded_bytes, 2621440);
+    assert_eq!(*total_bytes, Some(5242880));
+    assert_eq!(
+        tempfilename.as_deref(),
+        Some("Big Buck Bunny [aqz-KE-bpKQ].f137.mp4.part")
+    );
+    assert_eq!(*eta, Some(3));
+    assert_eq!(percent_str, " 50.0%");
+    assert_eq!(fixtures()[1].fraction(), Some(0.5));
+}
+
+#[test]
+fn fragmented_download_uses_estimate() {
+    let progress = &fixtures()[3];
+    let Progress::Downloading {
+        total_bytes,
+        fragment_index,
+        fragment_count,
+        ..
+    } = progress
+    else {
+        panic!("expected a downloading line");
+    };
+
+    assert_eq!(*total_bytes, None);
+    assert_eq!((*fragment_index, *fragment_count), (Some(3), Some(105)));
+    assert!(progress.fraction().is_some_and(|f| f > 0.028 && f < 0.029));
+    assert_eq!(fixtures()[4].fraction(), None);
+}
+
+#[test]
+fn finished_without_download() {
+    let Progress::Finished {
+        downloaded_bytes,
+        elapsed,
+        filename,
+        ..
+    } = &fixtures()[5]
+    else {
+        panic!("expected a finished line");
+    };
+
+    assert_eq!(*downloaded_bytes, None);
+    assert_eq!(*elapsed, None);
+    assert_eq!(filename, "Already Here [def456].mp4");
+}
+
+#[test]
+fn output_dispatches_json() {
+    for line in PROGRESS.lines() {
+        assert!(matches!(Output::from_str(line), Ok(Output::Progress(_))));
+    }
+}
+
+#[test]
+fn invalid_lines_are_errors() {
+    for line in INVALID.lines() {
+        let error = Progress::from_str(line).unwrap_err();
+        assert_eq!(error.content(), line);
+        assert!(matches!(
+            error.error(),
+            ParseErrorVariant::InvalidProgress(_)
+        ));
+    }
+}
+
+#[test]
+fn output_keeps_other_json_as_messages() {
+    let line = r#"{"id": "aqz-KE-bpKQ", "title": "Big Buck Bunny", "ext": "mp4"}"#;
+    assert!(matches!(
+        Output::from_str(line),
+        Ok(Output::Message(message)) if message.content() == line
+    ));
+
+    let line = r#"{"status": "downloading", "downloaded_bytes": "1024", "filename": "x.mp4"}"#;
+    assert!(matches!(
+        Output::from_str(line).unwrap_err().error(),
+        ParseErrorVariant::InvalidProgress(_)
+    ));
+}
+
+#[test]
+fn structured_progress_arguments() {
+    let mut builder = Builder::new("yt-dlp");
+    builder.structured_progress();
+
+    let args = builder
+        .command_mut()
+        .get_args()
+        .map(|arg| arg.to_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        args,
+        [
+            "--newline".to_string(),
+            "--progress-template".to_string(),
+            format!("download:{PROGRESS_PREFIX}%(progress)j"),
+        ]
+    );
+}
+
+#[test]
+fn output_dispatches_prefixed_json() {
+    for line in PROGRESS.lines() {
+        let line = format!("{PROGRESS_PREFIX}{line}");
+        assert!(matches!(Output::from_str(&line), Ok(Output::Progress(_))));
+    }
+
+    let line = format!("{PROGRESS_PREFIX}{{\"id\": \"aqz-KE-bpKQ\"}}");
+    assert_eq!(Output::from_str(&line).unwrap_err().content(), line);
+}
@@ -0,0 +1,155 @@
+use std::str::FromStr;
+
+use yt_dlp::{
+    Builder, CategorySelection, ChapterTitleField, OutputTemplate, ParseErrorVariant,
+    SponsorBlockCategories, SponsorBlockCategory, SponsorBlockChapterTitle, SponsorBlockRemoval,
+};
+
+#[test]
+fn builds_categories() {
+    let categories = SponsorBlockCategories::new()
+        .include(SponsorBlockCategory::All)
+        .exclude(SponsorBlockCategory::Preview)
+        .exclude(SponsorBlockCategory::MusicOfftopic);
+
+    assert_eq!(categories.to_string(), "all,-preview,-music_offtopic");
+}
+
+#[test]
+fn parses_categories() {
+    let categories = SponsorBlockCategories::from_str("sponsor,SelfPromo,-poi_highlight").unwrap();
+
+    assert_eq!(
+        categories.selections(),
+        [
+            CategorySelection {
+                category: SponsorBlockCategory::Sponsor,
+                exclude: false,
+            },
+            CategorySelection {
+                category: SponsorBlockCategory::SelfPromo,
+                exclude: false,
+            },
+            CategorySelection {
+                category: SponsorBlockCategory::PoiHighlight,
+                exclude: true,
+            },
+        ]
+    );
+    assert_eq!(categories.to_string(), "sponsor,selfpromo,-poi_highlight");
+
+    let error = SponsorBlockCategories::from_str("sponsor,self_promo").unwrap_err();
+    assert!(matches!(
+        error.error(),
+        ParseErrorVariant::UnknownSponsorBlockCategory(category) if category == "self_promo"
+    ));
+    assert_eq!(error.content(), "sponsor,self_promo");
+    assert!(SponsorBlockCategories::from_str("sponsor,").is_err());
+}
+
+#[test]
+fn removal_rejects_mark_only_categories() {
+    let removal = SponsorBlockRemoval::try_from(
+        SponsorBlockCategories::new()
+            .include(SponsorBlockCategory::All)
+            .exclude(SponsorBlockCategory::Filler),
+    )
+    .unwrap();
+    assert_eq!(removal.to_string(), "all,-filler");
+    assert_eq!(
+        SponsorBlockRemoval::from_str("default")
+            .unwrap()
+            .to_string(),
+        "default"
+    );
+
+    let error = SponsorBlockRemoval::try_from(SponsorBlockCategory::Chapter).unwrap_err();
+    assert!(matches!(
+        error.error(),
+        ParseErrorVariant::UnremovableSponsorBlockCategory(category) if category == "chapter"
+    ));
+
+    let error = SponsorBlockRemoval::from_str("sponsor,-POI_Highlight").unwrap_err();
+    assert!(matches!(
+        error.error(),
+        ParseErrorVariant::UnremovableSponsorBlockCategory(category) if category == "poi_highlight"
+    ));
+    assert_eq!(error.content(), "sponsor,-POI_Highlight");
+    assert!(matches!(
+        SponsorBlockRemoval::from_str("self_promo")
+            .unwrap_err()
+            .error(),
+        ParseErrorVariant::UnknownSponsorBlockCategory(_)
+    ));
+}
+
+#[test]
+fn builds_chapter_titles() {
+    let title = SponsorBlockChapterTitle::new()
+        .literal("[SponsorBlock]: ")
+        .field(ChapterTitleField::CategoryNames)
+        .literal(" from ")
+        .field(ChapterTitleField::StartTime);
+
+    assert_eq!(
+        title.to_string(),
+        "[SponsorBlock]: %(category_names)l from %(start_time)s"
+    );
+}
+
+#[test]
+fn parses_chapter_titles() {
+    let title = SponsorBlockChapterTitle::from_str("%(name)s (%(end_time-start_time)d s)").unwrap();
+    assert_eq!(
+        title.template(),
+        &OutputTemplate::from_str("%(name)s (%(end_time-start_time)d s)").unwrap()
+    );
+
+    for (template, field) in [
+        ("%(title)s", "title"),
+        ("%(category_names)l %(categorys)s", "categorys"),
+        ("%(end_time-duration)d", "duration"),
+    ] {
+        let error = SponsorBlockChapterTitle::from_str(template).unwrap_err();
+        assert!(matches!(
+            error.error(),
+            ParseErrorVariant::UnknownChapterTitleField(found) if found == field
+        ));
+        assert_eq!(error.content(), template);
+    }
+}
+
+#[test]
+fn sponsorblock_options_accept_typed_arguments() {
+    let mut builder = Builder::new("yt-dlp");
+    builder
+        .sponsorblock_mark(SponsorBlockCategory::All)
+        .sponsorblock_remove(
+            SponsorBlockRemoval::try_from(
+                SponsorBlockCategories::new()
+                    .include(SponsorBlockCategory::Sponsor)
+                    .include(SponsorBlockCategory::SelfPromo),
+            )
+            .unwrap(),
+        )
+        .sponsorblock_chapter_title(
+            SponsorBlockChapterTitle::new().field(ChapterTitleField::Category),
+        );
+
+    let args = builder
+        .command_mut()
+        .get_args()
+        .map(|arg| arg.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        args,
+        [
+            "--sponsorblock-mark",
+            "all",
+            "--sponsorblock-remove",
+            "sponsor,selfpromo",
+            "--sponsorblock-chapter-title",
+            "%(category)s"
+        ]
+    );
+}
@@ -0,0 +1,123 @@
+use std::str::FromStr;
+
+use yt_dlp::{Builder, DateBase, DateUnit, ParseErrorVariant, YtDate};
+
+#[test]
+fn renders_dates() {
+    assert_eq!(YtDate::ymd(2024, 1, 31).unwrap().to_string(), "20240131");
+    assert_eq!(YtDate::today().to_string(), "today");
+    assert_eq!(
+        DateBase::Today.ago(2, DateUnit::Week).to_string(),
+        "today-2weeks"
+    );
+    assert_eq!(
+        DateBase::Yesterday.ago(1, DateUnit::Month).to_string(),
+        "yesterday-1month"
+    );
+    assert_eq!(YtDate::from(DateBase::Now), YtDate::now());
+}
+
+#[test]
+fn validates_absolute_dates() {
+    assert!(YtDate::ymd(2024, 2, 29).is_some());
+    assert!(YtDate::ymd(2023, 2, 29).is_none());
+    assert!(YtDate::ymd(1900, 2, 29).is_none());
+    assert!(YtDate::ymd(2000, 2, 29).is_some());
+    assert!(YtDate::ymd(2024, 13, 1).is_none());
+    assert!(YtDate::ymd(2024, 4, 31).is_none());
+    assert!(YtDate::ymd(999, 1, 1).is_none());
+}
+
+#[test]
+fn parses_dates() {
+    assert_eq!(
+        YtDate::from_str("20240131").unwrap(),
+        YtDate::Absolute {
+            year: 2024,
+            month: 1,
+            day: 31
+        }
+    );
+    assert_eq!(
+        YtDate::from_str("now-3years").unwrap(),
+        YtDate::Relative {
+            base: DateBase::Now,
+            ago: Some((3, DateUnit::Year)),
+        }
+    );
+    assert_eq!(
+        YtDate::from_str("today-1day").unwrap(),
+        DateBase::Today.ago(1, DateUnit::Day)
+    );
+    assert_eq!(
+        YtDate::from_str("today-10days").unwrap(),
+        DateBase::Today.ago(10, DateUnit::Day)
+    );
+    assert_eq!(YtDate::from_str("yesterday").unwrap(), YtDate::yesterday());
+
+    for date in [
+        "2024-01-31",
+        "20240230",
+        "today+2weeks",
+        "today-weeks",
+        "today-2fortnights",
+        "tomorrow",
+        "20240131-1day",
+    ] {
+        let error = YtDate::from_str(date).unwrap_err();
+        assert!(matches!(error.error(), ParseErrorVariant::InvalidDate));
+        assert_eq!(error.content(), date);
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn from_chrono() {
+    let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+    assert_eq!(YtDate::try_from(date).unwrap().to_string(), "20240305");
+
+    let date = chrono::NaiveDate::from_ymd_opt(10000, 2, 29).unwrap();
+    assert!(matches!(
+        YtDate::try_from(date).unwrap_err().error(),
+        ParseErrorVariant::InvalidDate
+    ));
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn from_time() {
+    let date = time::Date::from_calendar_date(2024, time::Month::March, 5).unwrap();
+    assert_eq!(YtDate::try_from(date).unwrap().to_string(), "20240305");
+
+    let date = time::Date::from_calendar_date(999, time::Month::March, 5).unwrap();
+    assert!(matches!(
+        YtDate::try_from(date).unwrap_err().error(),
+        ParseErrorVariant::InvalidDate
+    ));
+}
+
+#[test]
+fn dates_as_arguments() {
+    let mut builder = Builder::new("yt-dlp");
+    builder
+        .dateafter(DateBase::Today.ago(2, DateUnit::Week))
+        .datebefore(YtDate::ymd(2024, 12, 31).unwrap())
+        .date("yesterday");
+
+    let args = builder
+        .command_mut()
+        .get_args()
+        .map(|arg| arg.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        args,
+        [
+            "--dateafter",
+            "today-2weeks",
+            "--datebefore",
+            "20241231",
+            "--date",
+            "yesterday"
+        ]
+    );
+}
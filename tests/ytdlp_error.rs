@@ -0,0 +1,96 @@
+use std::str::FromStr;
+
+use yt_dlp::{Message, YtDlpError};
+
+fn classify(line: &str) -> YtDlpError {
+    YtDlpError::from(Message::from_str(line).unwrap())
+}
+
+fn id() -> Option<String> {
+    Some("dQw4w9WgXcQ".to_string())
+}
+
+#[test]
+fn classifies_unavailable_videos() {
+    assert_eq!(
+        classify("ERROR: [youtube] dQw4w9WgXcQ: Video unavailable. This video has been removed by the uploader"),
+        YtDlpError::VideoUnavailable {
+            video_id: id(),
+            reason: "Video unavailable. This video has been removed by the uploader".to_string(),
+        }
+    );
+    assert_eq!(
+        classify("ERROR: [youtube] dQw4w9WgXcQ: Private video. Sign in if you've been granted access to this video"),
+        YtDlpError::PrivateVideo { video_id: id() }
+    );
+    assert_eq!(
+        classify("ERROR: [youtube] dQw4w9WgXcQ: Sign in to confirm your age. This video may be inappropriate for some users."),
+        YtDlpError::AgeRestricted { video_id: id() }
+    );
+    assert_eq!(
+        classify("ERROR: [youtube] dQw4w9WgXcQ: Requested format is not available. Use --list-formats for a list of available formats"),
+        YtDlpError::FormatUnavailable { video_id: id() }
+    );
+}
+
+#[test]
+fn classifies_geo_restrictions() {
+    assert_eq!(
+        classify("ERROR: [youtube] dQw4w9WgXcQ: The uploader has not made this video available in your country"),
+        YtDlpError::GeoRestricted {
+            video_id: id(),
+            countries: Vec::new(),
+        }
+    );
+
+    let error = classify("ERROR: [niconico] sm9: This video is not available from your location due to geo restriction. It is only available in JP, US and CA. You might want to use a VPN or a proxy server (with --proxy) to workaround.");
+    assert_eq!(
+        error,
+        YtDlpError::GeoRestricted {
+            video_id: Some("sm9".to_string()),
+            countries: vec!["JP".to_string(), "US".to_string(), "CA".to_string()],
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "sm9: video is only available in JP, US, CA"
+    );
+}
+
+#[test]
+fn classifies_http_errors() {
+    let error = classify("ERROR: [youtube] dQw4w9WgXcQ: Unable to download API page: HTTP Error 429: Too Many Requests (caused by <HTTPError 429: Too Many Requests>)");
+    assert_eq!(
+        error,
+        YtDlpError::Http {
+            video_id: id(),
+            status: 429,
+            reason: "Too Many Requests".to_string(),
+        }
+    );
+    assert!(error.is_retryable());
+
+    let error = classify("ERROR: unable to download video data: HTTP Error 403: Forbidden");
+    assert_eq!(
+        error,
+        YtDlpError::Http {
+            video_id: None,
+            status: 403,
+            reason: "Forbidden".to_string(),
+        }
+    );
+    assert!(!error.is_retryable());
+    assert_eq!(error.to_string(), "HTTP error 403: Forbidden");
+}
+
+#[test]
+fn keeps_unknown_messages() {
+    let message =
+        Message::from_str("ERROR: [generic] Unable to download webpage: <urlopen error timed out>")
+            .unwrap();
+    let error = YtDlpError::from(&message);
+
+    assert_eq!(error, YtDlpError::Unknown(message));
+    assert_eq!(error.video_id(), None);
+    assert!(!error.is_retryable());
+}
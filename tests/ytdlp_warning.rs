@@ -0,0 +1,80 @@
+use std::str::FromStr;
+
+use yt_dlp::{Message, WarningSeverity, YtDlpWarning};
+
+fn classify(line: &str) -> YtDlpWarning {
+    YtDlpWarning::from(Message::from_str(line).unwrap())
+}
+
+fn id() -> Option<String> {
+    Some("dQw4w9WgXcQ".to_string())
+}
+
+#[test]
+fn classifies_extraction_warnings() {
+    let warning = classify("WARNING: [generic] Falling back on generic information extractor");
+    assert_eq!(warning, YtDlpWarning::GenericExtractorFallback);
+    assert_eq!(warning.severity(), WarningSeverity::Degraded);
+
+    let warning = classify(
+        "WARNING: [youtube] dQw4w9WgXcQ: nsig extraction failed: Some formats may be missing",
+    );
+    assert_eq!(
+        warning,
+        YtDlpWarning::NsigExtractionFailed {
+            video_id: id(),
+            details: "Some formats may be missing".to_string(),
+        }
+    );
+    assert_eq!(warning.video_id(), Some("dQw4w9WgXcQ"));
+    assert_eq!(warning.severity(), WarningSeverity::Degraded);
+
+    let warning = classify("WARNING: [youtube] dQw4w9WgXcQ: Some formats are possibly damaged. They will be deprioritized");
+    assert_eq!(warning, YtDlpWarning::DamagedFormats { video_id: id() });
+    assert_eq!(warning.severity(), WarningSeverity::Info);
+}
+
+#[test]
+fn classifies_webpage_warnings() {
+    let warning = classify("WARNING: [youtube] dQw4w9WgXcQ: Unable to download webpage: HTTP Error 429: Too Many Requests (caused by <HTTPError 429: Too Many Requests>)");
+    assert_eq!(
+        warning,
+        YtDlpWarning::WebpageUnavailable {
+            video_id: id(),
+            status: Some(429),
+            reason: "Too Many Requests".to_string(),
+        }
+    );
+    assert_eq!(warning.severity(), WarningSeverity::Critical);
+
+    let warning = classify("WARNING: [youtube] dQw4w9WgXcQ: Unable to download webpage: <urlopen error [Errno -2] Name or service not known> (caused by TransportError())");
+    assert_eq!(
+        warning,
+        YtDlpWarning::WebpageUnavailable {
+            video_id: id(),
+            status: None,
+            reason: "<urlopen error [Errno -2] Name or service not known>".to_string(),
+        }
+    );
+    assert_eq!(warning.severity(), WarningSeverity::Degraded);
+}
+
+#[test]
+fn classifies_missing_ffmpeg() {
+    let warning = classify("WARNING: ffmpeg not found. The downloaded format may not be the best available. Installing ffmpeg is strongly recommended: https://github.com/yt-dlp/yt-dlp#dependencies");
+    assert_eq!(warning, YtDlpWarning::FfmpegNotFound);
+    assert_eq!(warning.severity(), WarningSeverity::Critical);
+    assert_eq!(warning.to_string(), "ffmpeg not found");
+}
+
+#[test]
+fn keeps_unknown_warnings() {
+    let line = "WARNING: [youtube] dQw4w9WgXcQ: Native nsig is not supported";
+    let warning = classify(line);
+    assert_eq!(
+        warning,
+        YtDlpWarning::Unknown(Message::from_str(line).unwrap())
+    );
+    assert_eq!(warning.severity(), WarningSeverity::Info);
+    assert!(WarningSeverity::Info < WarningSeverity::Critical);
+}